@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use secure_string::SecureBytes;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +14,175 @@ pub struct Login {
     pub password: SecureBytes,
 }
 
+/// Check a password against the password policy without creating an account.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct RatePassword {
+    pub password: SecureBytes,
+}
+
+/// The result of checking a password against the password policy.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct PasswordRating {
+    /// True if the password would be accepted when creating an account.
+    pub acceptable: bool,
+    pub too_short: bool,
+    pub too_long: bool,
+    /// Whether the password appears in a data breach.
+    /// This is `None` if the breach check is disabled or currently unavailable,
+    /// or if too many passwords were rated recently.
+    pub compromised: Option<bool>,
+}
+
+/// Information about the logged in user.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct WhoAmI {
+    pub username: String,
+    pub last_login_at: DateTime<Utc>,
+}
+
+/// An overview of the security-relevant state of the logged in user's account.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct AccountSecurity {
+    pub last_login_at: DateTime<Utc>,
+    /// The number of sessions that are logged in and not expired.
+    pub active_session_count: u64,
+    /// The number of API tokens that are not expired.
+    pub api_token_count: u64,
+}
+
+/// Create an API token for clients that cannot use the session cookie.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct CreateApiToken {
+    pub label: String,
+    /// If not given, then the token does not expire.
+    pub expiry: Option<DateTime<Utc>>,
+}
+
+/// A newly created API token.
+/// The token itself cannot be retrieved again later.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct CreatedApiToken {
+    pub id: i32,
+    pub token: String,
+}
+
+/// Information about an existing API token.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct ApiTokenInfo {
+    pub id: i32,
+    pub label: String,
+    pub created_at: DateTime<Utc>,
+    pub expiry: Option<DateTime<Utc>>,
+}
+
+/// The current time of the server.
+/// Clients can use this to compute the skew between their clock and the server clock.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct ServerTime {
+    pub time: DateTime<Utc>,
+}
+
+/// The optional features and the limits of the server.
+/// Clients can use this to only show the features that are available.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct Capabilities {
+    /// Whether new passwords are rejected if they appear in a data breach.
+    pub compromised_password_check: bool,
+    /// Whether accounts that are not logged into for a long time lose their password and sessions.
+    pub account_inactivity_expiry: bool,
+    pub minimum_username_length: usize,
+    pub maximum_username_length: usize,
+    pub minimum_password_length: usize,
+    pub maximum_password_length: usize,
+    pub maximum_api_token_label_length: usize,
+    pub maximum_word_note_length: usize,
+    pub maximum_word_report_reason_length: usize,
+    /// The maximum number of words that can be queried at once.
+    pub maximum_word_batch_size: usize,
+}
+
+/// Identifies a word by its spelling, its language and its word type.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct WordIdentifier {
+    pub language: String,
+    pub word: String,
+    pub word_type: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct SetWordNote {
+    #[serde(flatten)]
+    pub word: WordIdentifier,
+    pub note: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct WordNote {
+    pub note: String,
+}
+
+/// Identifies a word by its spelling and its language, independent of its word type.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct LanguageWord {
+    pub language: String,
+    pub word: String,
+}
+
+/// Query the languages that contain a word.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct DetectLanguage {
+    pub word: String,
+}
+
+/// Report a problem with the dictionary data, e.g. a wrong word type or a missing word.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct ReportWord {
+    #[serde(flatten)]
+    pub word: WordIdentifier,
+    pub reason: String,
+}
+
+/// Query the translations of a word into another language.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct ListTranslations {
+    /// The english name of the language to translate into.
+    pub to: String,
+}
+
+/// Query a random word, optionally restricted to a language and a word type.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct RandomWord {
+    pub language: Option<String>,
+    pub word_type: Option<String>,
+}
+
+/// Query the most common words of a language.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct ListCommonWords {
+    pub language: String,
+    pub limit: usize,
+}
+
+/// The number of words in a language.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct LanguageWordCount {
+    pub language: String,
+    pub word_count: i64,
+}
+
+/// The number of words of a word type in a language.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct WordTypeCount {
+    pub word_type: String,
+    pub word_count: i64,
+}
+
+/// The body of an error response.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
 #[cfg(test)]
 mod tests {
     use crate::CreateAccount;
@@ -0,0 +1,61 @@
+-- Dictionary data used by the integration tests.
+-- The wiktionary import does not run during integration tests, so this is the only dictionary data available.
+
+INSERT INTO languages (english_name) VALUES ('English'), ('French');
+INSERT INTO word_types (english_name) VALUES ('Noun'), ('Verb'), ('Adjective');
+
+INSERT INTO words (word, word_type, language)
+SELECT seed.word, word_types.id, languages.id
+FROM (VALUES
+	('run', 'Noun', 'English'),
+	('run', 'Verb', 'English'),
+	('house', 'Noun', 'English'),
+	('green', 'Adjective', 'English'),
+	('maison', 'Noun', 'French'),
+	('courir', 'Verb', 'French')
+) AS seed (word, word_type, language)
+JOIN word_types ON word_types.english_name = seed.word_type
+JOIN languages ON languages.english_name = seed.language;
+
+-- Frequency ranks as set by the ImportFrequencyList command.
+UPDATE words SET frequency_rank = seed.frequency_rank
+FROM (VALUES
+	('house', 1),
+	('run', 2),
+	('green', 3)
+) AS seed (word, frequency_rank)
+WHERE words.word = seed.word
+AND words.language = (SELECT id FROM languages WHERE english_name = 'English');
+
+-- Inflected forms, which the wiktionary import does not provide yet.
+INSERT INTO word_forms (language, form, lemma)
+SELECT languages.id, seed.form, seed.lemma
+FROM (VALUES
+	('running', 'run', 'English'),
+	('ran', 'run', 'English'),
+	('houses', 'house', 'English'),
+	('courant', 'courir', 'French')
+) AS seed (form, lemma, language)
+JOIN languages ON languages.english_name = seed.language;
+
+-- A spelling that exists in more than one language, with a higher frequency rank in the second language.
+INSERT INTO languages (english_name) VALUES ('Scots');
+
+INSERT INTO words (word, word_type, language, frequency_rank)
+SELECT 'green', word_types.id, languages.id, 1
+FROM word_types, languages
+WHERE word_types.english_name = 'Adjective' AND languages.english_name = 'Scots';
+
+-- Translations, which the wiktionary import does not provide yet.
+INSERT INTO translations (word, word_type, language, translation_word, translation_word_type, translation_language)
+SELECT seed.word, word_types.id, languages.id, seed.translation_word, word_types.id, translation_languages.id
+FROM (VALUES
+	('maison', 'French', 'house', 'English', 'Noun'),
+	('courir', 'French', 'run', 'English', 'Verb')
+) AS seed (word, language, translation_word, translation_language, word_type)
+JOIN word_types ON word_types.english_name = seed.word_type
+JOIN languages ON languages.english_name = seed.language
+JOIN languages AS translation_languages ON translation_languages.english_name = seed.translation_language;
+
+-- The word statistics are otherwise only computed by the RefreshWordStats job.
+REFRESH MATERIALIZED VIEW language_word_counts;
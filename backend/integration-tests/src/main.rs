@@ -1,5 +1,12 @@
+use std::collections::HashSet;
+
 use anyhow::{bail, Context};
-use api_commands::{CreateAccount, Login};
+use api_commands::{
+    AccountSecurity, ApiTokenInfo, Capabilities, CreateAccount, CreateApiToken, CreatedApiToken,
+    DetectLanguage, ErrorResponse, LanguageWord, LanguageWordCount, ListCommonWords,
+    ListTranslations, Login, PasswordRating, RandomWord, RatePassword, ReportWord, ServerTime,
+    SetWordNote, WhoAmI, WordIdentifier, WordNote, WordTypeCount,
+};
 use log::{debug, error, info};
 use reqwest::StatusCode;
 use secure_string::SecureBytes;
@@ -29,15 +36,43 @@ async fn main() -> anyhow::Result<()> {
     let tasks = [
         spawn(test_user_account_creation()),
         spawn(test_duplicate_user_account_creation()),
+        spawn(test_concurrent_duplicate_user_account_creation()),
         spawn(test_user_account_deletion()),
         spawn(test_login_logout()),
         spawn(test_wrong_password()),
+        spawn(test_last_login_at()),
+        spawn(test_api_tokens()),
+        spawn(test_account_security()),
         spawn(test_too_long_username()),
         spawn(test_too_long_password()),
+        spawn(test_rate_password()),
         spawn(test_too_short_username()),
         spawn(test_too_short_password()),
         spawn(test_wrong_username_login()),
         spawn(test_too_long_password_login()),
+        spawn(test_maximum_sessions_per_user()),
+        spawn(test_security_headers()),
+        spawn(test_session_cookie_attributes()),
+        spawn(test_session_renewal_interval()),
+        spawn(test_unknown_route()),
+        spawn(test_server_time()),
+        spawn(test_capabilities()),
+        spawn(test_trailing_slash()),
+        spawn(test_wrong_content_type()),
+        spawn(test_malformed_json()),
+        spawn(test_http2()),
+        spawn(test_word_notes()),
+        spawn(test_word_note_quota()),
+        spawn(test_word_reports()),
+        spawn(test_language_word_types()),
+        spawn(test_languages()),
+        spawn(test_common_words()),
+        spawn(test_words_exist()),
+        spawn(test_lemmas()),
+        spawn(test_translations()),
+        spawn(test_detect_language()),
+        spawn(test_random_word()),
+        spawn(test_allowed_origins()),
     ];
     let test_amount = tasks.len();
 
@@ -115,6 +150,28 @@ async fn test_duplicate_user_account_creation() -> anyhow::Result<()> {
     assert_response_status!(response, StatusCode::CONFLICT)
 }
 
+async fn test_concurrent_duplicate_user_account_creation() -> anyhow::Result<()> {
+    let client_a = HttpClient::new().await?;
+    let client_b = HttpClient::new().await?;
+    let create_account = || CreateAccount {
+        username: "clara".to_owned(),
+        password: "zetkin".to_owned().into(),
+    };
+
+    let (response_a, response_b) = tokio::join!(
+        client_a.post("/accounts/create", create_account()),
+        client_b.post("/accounts/create", create_account()),
+    );
+    let mut statuses = [response_a?.status(), response_b?.status()];
+    statuses.sort();
+
+    if statuses != [StatusCode::CREATED, StatusCode::CONFLICT] {
+        bail!("Expected one account creation to succeed and one to conflict, but got {statuses:?}");
+    }
+
+    Ok(())
+}
+
 async fn test_user_account_deletion() -> anyhow::Result<()> {
     let client = HttpClient::new().await?;
     let response = client
@@ -270,6 +327,354 @@ async fn test_wrong_password() -> anyhow::Result<()> {
     assert_response_status!(response, StatusCode::UNAUTHORIZED)
 }
 
+async fn test_last_login_at() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let other_client = HttpClient::new().await?;
+    let login = || Login {
+        username: "lise".to_owned(),
+        password: "meitner".to_owned().into(),
+    };
+
+    let response = client
+        .post(
+            "/accounts/create",
+            CreateAccount {
+                username: "lise".to_owned(),
+                password: "meitner".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    let response = client.post("/accounts/login", login()).await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let response = client.get_empty("/accounts/whoami").await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let first_whoami: WhoAmI = response.json().await?;
+    if first_whoami.username != "lise" {
+        bail!("Wrong username in whoami: {first_whoami:?}");
+    }
+
+    // a failed login does not update the timestamp
+    let response = other_client
+        .post(
+            "/accounts/login",
+            Login {
+                username: "lise".to_owned(),
+                password: "hahn".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::BAD_REQUEST)?;
+
+    let response = client.get_empty("/accounts/whoami").await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let second_whoami: WhoAmI = response.json().await?;
+    if second_whoami != first_whoami {
+        bail!("Failed login changed whoami from {first_whoami:?} to {second_whoami:?}");
+    }
+
+    // a successful login updates the timestamp
+    let response = other_client.post("/accounts/login", login()).await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let response = client.get_empty("/accounts/whoami").await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let third_whoami: WhoAmI = response.json().await?;
+    if third_whoami.last_login_at <= first_whoami.last_login_at {
+        bail!("Login did not update whoami from {first_whoami:?} to {third_whoami:?}");
+    }
+
+    // whoami requires login
+    let response = HttpClient::new()
+        .await?
+        .get_empty("/accounts/whoami")
+        .await?;
+    assert_response_status!(response, StatusCode::UNAUTHORIZED)
+}
+
+async fn test_api_tokens() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let token_client = HttpClient::new().await?;
+
+    let response = client
+        .post(
+            "/accounts/create",
+            CreateAccount {
+                username: "ada".to_owned(),
+                password: "lovelace".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    // creating tokens requires login
+    let response = client
+        .post(
+            "/accounts/tokens",
+            CreateApiToken {
+                label: "cli".to_owned(),
+                expiry: None,
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::UNAUTHORIZED)?;
+
+    let response = client
+        .post(
+            "/accounts/login",
+            Login {
+                username: "ada".to_owned(),
+                password: "lovelace".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let response = client
+        .post(
+            "/accounts/tokens",
+            CreateApiToken {
+                label: "a".repeat(101),
+                expiry: None,
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::BAD_REQUEST)?;
+
+    let response = client
+        .post(
+            "/accounts/tokens",
+            CreateApiToken {
+                label: "cli".to_owned(),
+                expiry: None,
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::CREATED)?;
+    let created_token: CreatedApiToken = response.json().await?;
+    let authorization = format!("Bearer {}", created_token.token);
+
+    // an expired token is rejected
+    let response = client
+        .post(
+            "/accounts/tokens",
+            CreateApiToken {
+                label: "expired".to_owned(),
+                expiry: Some(chrono::Utc::now() - chrono::Duration::try_hours(1).unwrap()),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::CREATED)?;
+    let expired_token: CreatedApiToken = response.json().await?;
+    let response = token_client
+        .get_empty_with_header(
+            "/accounts/whoami",
+            "Authorization",
+            &format!("Bearer {}", expired_token.token),
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::UNAUTHORIZED)?;
+
+    let response = client.get_empty("/accounts/tokens").await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let api_tokens: Vec<ApiTokenInfo> = response.json().await?;
+    let labels: Vec<_> = api_tokens
+        .iter()
+        .map(|api_token| api_token.label.as_str())
+        .collect();
+    if labels != ["cli", "expired"] || api_tokens[0].id != created_token.id {
+        bail!("Wrong api tokens: {api_tokens:?}");
+    }
+
+    // the token authenticates without a session
+    let response = token_client
+        .get_empty_with_header("/accounts/whoami", "Authorization", &authorization)
+        .await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let whoami: WhoAmI = response.json().await?;
+    if whoami.username != "ada" {
+        bail!("Wrong username in whoami: {whoami:?}");
+    }
+
+    // the token cannot manage tokens or delete the account
+    let response = token_client
+        .get_empty_with_header("/accounts/tokens", "Authorization", &authorization)
+        .await?;
+    assert_response_status!(response, StatusCode::FORBIDDEN)?;
+
+    let response = token_client
+        .post_with_header(
+            "/accounts/tokens",
+            CreateApiToken {
+                label: "from token".to_owned(),
+                expiry: None,
+            },
+            "Authorization",
+            &authorization,
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::FORBIDDEN)?;
+
+    let response = token_client
+        .delete_with_header(
+            &format!("/accounts/tokens/{}", created_token.id),
+            "Authorization",
+            &authorization,
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::FORBIDDEN)?;
+
+    let response = token_client
+        .delete_with_header("/accounts/delete", "Authorization", &authorization)
+        .await?;
+    assert_response_status!(response, StatusCode::FORBIDDEN)?;
+
+    let response = token_client
+        .get_empty_with_header("/accounts/whoami", "Authorization", "Bearer wrong")
+        .await?;
+    assert_response_status!(response, StatusCode::UNAUTHORIZED)?;
+
+    let response = token_client
+        .get_empty_with_header("/accounts/whoami", "Authorization", "Basic abc")
+        .await?;
+    assert_response_status!(response, StatusCode::UNAUTHORIZED)?;
+
+    // revoking the token
+    let response = client
+        .delete(&format!("/accounts/tokens/{}", created_token.id))
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let response = client
+        .delete(&format!("/accounts/tokens/{}", created_token.id))
+        .await?;
+    assert_response_status!(response, StatusCode::NOT_FOUND)?;
+
+    let response = token_client
+        .get_empty_with_header("/accounts/whoami", "Authorization", &authorization)
+        .await?;
+    assert_response_status!(response, StatusCode::UNAUTHORIZED)
+}
+
+async fn test_rate_password() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let rate_password = |password: &str| RatePassword {
+        password: password.to_owned().into(),
+    };
+
+    let response = client
+        .post("/accounts/password-strength", rate_password("franklin"))
+        .await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let rating: PasswordRating = response.json().await?;
+    if !rating.acceptable || rating.too_short || rating.too_long {
+        bail!("Strong password was rated as weak: {rating:?}");
+    }
+
+    let response = client
+        .post("/accounts/password-strength", rate_password("dna"))
+        .await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let rating: PasswordRating = response.json().await?;
+    if rating.acceptable || !rating.too_short || rating.too_long {
+        bail!("Short password was not rated as too short: {rating:?}");
+    }
+
+    let response = client
+        .post(
+            "/accounts/password-strength",
+            rate_password(&"franklin".repeat(100)),
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let rating: PasswordRating = response.json().await?;
+    if rating.acceptable || rating.too_short || !rating.too_long {
+        bail!("Long password was not rated as too long: {rating:?}");
+    }
+
+    Ok(())
+}
+
+async fn test_account_security() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let second_client = HttpClient::new().await?;
+
+    let response = client.get_empty("/accounts/security").await?;
+    assert_response_status!(response, StatusCode::UNAUTHORIZED)?;
+
+    let response = client
+        .post(
+            "/accounts/create",
+            CreateAccount {
+                username: "rosalind".to_owned(),
+                password: "franklin".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    let before = chrono::Utc::now();
+    for client in [&client, &second_client] {
+        let response = client
+            .post(
+                "/accounts/login",
+                Login {
+                    username: "rosalind".to_owned(),
+                    password: "franklin".to_owned().into(),
+                },
+            )
+            .await?;
+        assert_response_status!(response, StatusCode::NO_CONTENT)?;
+    }
+    let after = chrono::Utc::now();
+
+    for expiry in [
+        None,
+        Some(chrono::Utc::now() - chrono::Duration::try_hours(1).unwrap()),
+    ] {
+        let response = client
+            .post(
+                "/accounts/tokens",
+                CreateApiToken {
+                    label: "cli".to_owned(),
+                    expiry,
+                },
+            )
+            .await?;
+        assert_response_status!(response, StatusCode::CREATED)?;
+    }
+
+    let response = client.get_empty("/accounts/security").await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let security: AccountSecurity = response.json().await?;
+    // the expired token is not counted
+    if security.active_session_count != 2 || security.api_token_count != 1 {
+        bail!("unexpected account security overview: {security:?}");
+    }
+    let tolerance = chrono::Duration::try_seconds(1).unwrap();
+    if security.last_login_at < before - tolerance || security.last_login_at > after + tolerance {
+        bail!(
+            "last login at {} is not between {before} and {after}",
+            security.last_login_at
+        );
+    }
+
+    // logging out ends one of the sessions
+    let response = second_client.post_empty("/accounts/logout").await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let response = client.get_empty("/accounts/security").await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let security: AccountSecurity = response.json().await?;
+    if security.active_session_count != 1 {
+        bail!("unexpected account security overview after logout: {security:?}");
+    }
+
+    Ok(())
+}
+
 async fn test_too_long_username() -> anyhow::Result<()> {
     let client = HttpClient::new().await?;
     let response = client
@@ -374,3 +779,1094 @@ async fn test_too_long_password_login() -> anyhow::Result<()> {
 
     assert_response_status!(response, StatusCode::BAD_REQUEST)
 }
+
+/// Requires the backend to be started with `MAXIMUM_SESSIONS_PER_USER=3`.
+async fn test_maximum_sessions_per_user() -> anyhow::Result<()> {
+    let maximum_sessions_per_user = 3;
+    let mut clients = Vec::new();
+    for _ in 0..maximum_sessions_per_user + 1 {
+        clients.push(HttpClient::new().await?);
+    }
+
+    let response = clients[0]
+        .post(
+            "/accounts/create",
+            CreateAccount {
+                username: "erich".to_owned(),
+                password: "mühsam😀😀😀".to_owned().into(),
+            },
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    for client in &clients {
+        let response = client
+            .post(
+                "/accounts/login",
+                Login {
+                    username: "erich".to_owned(),
+                    password: "mühsam😀😀😀".to_owned().into(),
+                },
+            )
+            .await?;
+
+        assert_response_status!(response, StatusCode::NO_CONTENT)?;
+    }
+
+    // the oldest session was evicted
+    let response = clients[0].post_empty("/accounts/logout").await?;
+
+    assert_response_status!(response, StatusCode::UNAUTHORIZED)?;
+
+    // the other sessions are still valid
+    for client in &clients[1..] {
+        let response = client.post_empty("/accounts/logout").await?;
+
+        assert_response_status!(response, StatusCode::NO_CONTENT)?;
+    }
+
+    Ok(())
+}
+
+async fn test_security_headers() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let response = client.post_empty("/accounts/logout").await?;
+
+    for (header, expected) in [
+        ("x-content-type-options", "nosniff"),
+        ("x-frame-options", "DENY"),
+        ("referrer-policy", "no-referrer"),
+        (
+            "content-security-policy",
+            "default-src 'none'; frame-ancestors 'none'",
+        ),
+    ] {
+        let actual = response.headers().get(header);
+        if actual.map(|value| value.as_bytes()) != Some(expected.as_bytes()) {
+            bail!("unexpected value for header {header}: {actual:?}\nexpected: {expected:?}");
+        }
+    }
+
+    assert_response_status!(response, StatusCode::UNAUTHORIZED)
+}
+
+/// The integration tests run via plain HTTP, so the backend is started with `COOKIE_SECURE=false`.
+async fn test_session_cookie_attributes() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let response = client
+        .post(
+            "/accounts/create",
+            CreateAccount {
+                username: "hypatia".to_owned(),
+                password: "alexandria".to_owned().into(),
+            },
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    // Creating an account does not log in, so the session cookie is only set by the login.
+    let response = client
+        .post(
+            "/accounts/login",
+            Login {
+                username: "hypatia".to_owned(),
+                password: "alexandria".to_owned().into(),
+            },
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let Some(cookie) = response.headers().get("set-cookie") else {
+        bail!("no session cookie was set");
+    };
+    let cookie = cookie.to_str()?;
+    let attributes: Vec<_> = cookie
+        .split(';')
+        .map(|attribute| attribute.trim().to_ascii_lowercase())
+        .collect();
+    if attributes.iter().any(|attribute| attribute == "secure") {
+        bail!("session cookie has the Secure attribute: {cookie:?}");
+    }
+
+    Ok(())
+}
+
+/// Requires the backend to be started with `SESSION_MINIMUM_RENEWAL_INTERVAL_SECONDS=5`.
+async fn test_session_renewal_interval() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let response = client
+        .post(
+            "/accounts/create",
+            CreateAccount {
+                username: "grete".to_owned(),
+                password: "hermann".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    let response = client
+        .post(
+            "/accounts/login",
+            Login {
+                username: "grete".to_owned(),
+                password: "hermann".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+    let Some(login_session) = session_cookie(&response)? else {
+        bail!("no session cookie was set by the login");
+    };
+
+    // A renewal rotates the session id, so it sets a cookie with a new session id.
+    for _ in 0..5 {
+        let response = client.get_empty("/accounts/whoami").await?;
+        if let Some(session) = session_cookie(&response)? {
+            if session != login_session {
+                bail!("session was renewed right after login: {session:?}");
+            }
+        }
+        assert_response_status!(response, StatusCode::OK)?;
+    }
+
+    tokio::time::sleep(std::time::Duration::from_secs(6)).await;
+
+    let response = client.get_empty("/accounts/whoami").await?;
+    let Some(renewed_session) = session_cookie(&response)? else {
+        bail!("session was not renewed after the minimum renewal interval");
+    };
+    if renewed_session == login_session {
+        bail!("session was renewed without rotating its id");
+    }
+    assert_response_status!(response, StatusCode::OK)?;
+
+    let response = client.get_empty("/accounts/whoami").await?;
+    if let Some(session) = session_cookie(&response)? {
+        if session != renewed_session {
+            bail!("session was renewed again right after its renewal: {session:?}");
+        }
+    }
+    assert_response_status!(response, StatusCode::OK)
+}
+
+/// The `name=value` pair of the cookie set by the response, if any.
+fn session_cookie(response: &reqwest::Response) -> anyhow::Result<Option<String>> {
+    let Some(cookie) = response.headers().get("set-cookie") else {
+        return Ok(None);
+    };
+    let cookie = cookie.to_str()?;
+    Ok(cookie
+        .split(';')
+        .next()
+        .map(|name_value| name_value.trim().to_owned()))
+}
+
+async fn test_http2() -> anyhow::Result<()> {
+    let client = HttpClient::new_http2().await?;
+    let response = client
+        .post(
+            "/accounts/create",
+            CreateAccount {
+                username: "margarete".to_owned(),
+                password: "buber-neumann".to_owned().into(),
+            },
+        )
+        .await?;
+
+    if response.version() != reqwest::Version::HTTP_2 {
+        bail!("unexpected HTTP version: {:?}", response.version());
+    }
+
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    let response = client
+        .post(
+            "/accounts/login",
+            Login {
+                username: "margarete".to_owned(),
+                password: "buber-neumann".to_owned().into(),
+            },
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let response = client.post_empty("/accounts/logout").await?;
+
+    assert_response_status!(response, StatusCode::NO_CONTENT)
+}
+
+async fn test_word_notes() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let response = client
+        .post(
+            "/accounts/create",
+            CreateAccount {
+                username: "sophie".to_owned(),
+                password: "scholl😀😀😀".to_owned().into(),
+            },
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    let response = client
+        .post(
+            "/accounts/login",
+            Login {
+                username: "sophie".to_owned(),
+                password: "scholl😀😀😀".to_owned().into(),
+            },
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let word = WordIdentifier {
+        language: "English".to_owned(),
+        word: "run".to_owned(),
+        word_type: "Verb".to_owned(),
+    };
+
+    // no note yet
+    let response = client.get("/words/note", &word).await?;
+
+    assert_response_status!(response, StatusCode::NOT_FOUND)?;
+
+    // create
+    let response = client
+        .put(
+            "/words/note",
+            SetWordNote {
+                word: word.clone(),
+                note: "like a rabbit".to_owned(),
+            },
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let response = client.get("/words/note", &word).await?;
+    let note: WordNote = response.json().await?;
+    if note.note != "like a rabbit" {
+        bail!("unexpected note: {note:?}");
+    }
+
+    // update
+    let response = client
+        .put(
+            "/words/note",
+            SetWordNote {
+                word: word.clone(),
+                note: "like a fox".to_owned(),
+            },
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let response = client.get("/words/note", &word).await?;
+    let note: WordNote = response.json().await?;
+    if note.note != "like a fox" {
+        bail!("unexpected note: {note:?}");
+    }
+
+    // too long
+    let response = client
+        .put(
+            "/words/note",
+            SetWordNote {
+                word: word.clone(),
+                note: "fox".repeat(1000),
+            },
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::BAD_REQUEST)?;
+
+    // the word type is part of the word
+    let response = client
+        .get(
+            "/words/note",
+            WordIdentifier {
+                word_type: "Noun".to_owned(),
+                ..word.clone()
+            },
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::NOT_FOUND)?;
+
+    // unknown word
+    let response = client
+        .put(
+            "/words/note",
+            SetWordNote {
+                word: WordIdentifier {
+                    word: "rnu".to_owned(),
+                    ..word.clone()
+                },
+                note: "typo".to_owned(),
+            },
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::NOT_FOUND)
+}
+
+/// Requires the backend to be started with `MAXIMUM_OPEN_WORD_REPORTS_PER_USER=2`.
+async fn test_word_reports() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let report = |word: &str, reason: String| ReportWord {
+        word: WordIdentifier {
+            language: "English".to_owned(),
+            word: word.to_owned(),
+            word_type: "Noun".to_owned(),
+        },
+        reason,
+    };
+
+    // reporting requires login
+    let response = client
+        .post("/words/report", report("house", "wrong".to_owned()))
+        .await?;
+    assert_response_status!(response, StatusCode::UNAUTHORIZED)?;
+
+    let response = client
+        .post(
+            "/accounts/create",
+            CreateAccount {
+                username: "katherine".to_owned(),
+                password: "johnson".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::CREATED)?;
+    let response = client
+        .post(
+            "/accounts/login",
+            Login {
+                username: "katherine".to_owned(),
+                password: "johnson".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let response = client
+        .post("/words/report", report("house", "a".repeat(1001)))
+        .await?;
+    assert_response_status!(response, StatusCode::BAD_REQUEST)?;
+
+    let response = client
+        .post("/words/report", report("house", "also a verb".to_owned()))
+        .await?;
+    assert_response_status!(response, StatusCode::CREATED)?;
+    // missing words can be reported as well
+    let response = client
+        .post("/words/report", report("trajectory", "missing".to_owned()))
+        .await?;
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    // the quota of unresolved reports is reached
+    let response = client
+        .post("/words/report", report("run", "also a verb".to_owned()))
+        .await?;
+    assert_response_status!(response, StatusCode::FORBIDDEN)?;
+
+    // reports can only be listed by operators via the cli
+    let response = client.get_empty("/words/report").await?;
+    assert_response_status!(response, StatusCode::METHOD_NOT_ALLOWED)
+}
+
+/// Requires the backend to be started with `MAXIMUM_WORD_NOTES_PER_USER=2`.
+async fn test_word_note_quota() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let response = client
+        .post(
+            "/accounts/create",
+            CreateAccount {
+                username: "emmy".to_owned(),
+                password: "noether😀😀😀".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    let response = client
+        .post(
+            "/accounts/login",
+            Login {
+                username: "emmy".to_owned(),
+                password: "noether😀😀😀".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let word = |word: &str, word_type: &str| WordIdentifier {
+        language: "English".to_owned(),
+        word: word.to_owned(),
+        word_type: word_type.to_owned(),
+    };
+    let set_word_note = |word: WordIdentifier| SetWordNote {
+        word,
+        note: "ring".to_owned(),
+    };
+
+    let response = client
+        .put("/words/note", set_word_note(word("run", "Verb")))
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+    let response = client
+        .put("/words/note", set_word_note(word("run", "Noun")))
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    // the quota is reached
+    let response = client
+        .put("/words/note", set_word_note(word("house", "Noun")))
+        .await?;
+    assert_response_status!(response, StatusCode::FORBIDDEN)?;
+
+    // existing notes can still be updated
+    let response = client
+        .put("/words/note", set_word_note(word("run", "Verb")))
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    // deleting a note frees up room
+    let response = client
+        .delete_with_query("/words/note", word("run", "Noun"))
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+    let response = client
+        .delete_with_query("/words/note", word("run", "Noun"))
+        .await?;
+    assert_response_status!(response, StatusCode::NOT_FOUND)?;
+
+    let response = client
+        .put("/words/note", set_word_note(word("house", "Noun")))
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)
+}
+
+/// Requires the backend to be started with `API_ALLOWED_ORIGINS=http://localhost:8093`.
+async fn test_allowed_origins() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+
+    // allowed origin
+    let response = client
+        .post_with_header(
+            "/accounts/create",
+            CreateAccount {
+                username: "helmuth".to_owned(),
+                password: "hübener😀😀😀".to_owned().into(),
+            },
+            "Origin",
+            "http://localhost:8093",
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    // disallowed origin
+    let response = client
+        .post_with_header(
+            "/accounts/login",
+            Login {
+                username: "helmuth".to_owned(),
+                password: "hübener😀😀😀".to_owned().into(),
+            },
+            "Origin",
+            "http://evil.example.com",
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::FORBIDDEN)?;
+
+    // disallowed referer
+    let response = client
+        .post_with_header(
+            "/accounts/login",
+            Login {
+                username: "helmuth".to_owned(),
+                password: "hübener😀😀😀".to_owned().into(),
+            },
+            "Referer",
+            "http://evil.example.com/accounts/login",
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::FORBIDDEN)?;
+
+    // the rejected requests did not log in
+    let response = client.post_empty("/accounts/logout").await?;
+
+    assert_response_status!(response, StatusCode::UNAUTHORIZED)?;
+
+    // absent origin
+    let response = client
+        .post(
+            "/accounts/login",
+            Login {
+                username: "helmuth".to_owned(),
+                password: "hübener😀😀😀".to_owned().into(),
+            },
+        )
+        .await?;
+
+    assert_response_status!(response, StatusCode::NO_CONTENT)
+}
+
+/// Requires the dictionary data from `seed.sql`.
+async fn test_language_word_types() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+
+    let response = client.get_empty("/languages/English/word-types").await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let word_type_counts: Vec<WordTypeCount> = response.json().await?;
+    let expected = [("Adjective", 1), ("Noun", 2), ("Verb", 1)]
+        .map(|(word_type, word_count)| WordTypeCount {
+            word_type: word_type.to_owned(),
+            word_count,
+        })
+        .to_vec();
+    if word_type_counts != expected {
+        bail!("Expected word type counts {expected:?}, but got {word_type_counts:?}");
+    }
+
+    let response = client.get_empty("/languages/French/word-types").await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let word_type_counts: Vec<WordTypeCount> = response.json().await?;
+    let expected = [("Noun", 1), ("Verb", 1)]
+        .map(|(word_type, word_count)| WordTypeCount {
+            word_type: word_type.to_owned(),
+            word_count,
+        })
+        .to_vec();
+    if word_type_counts != expected {
+        bail!("Expected word type counts {expected:?}, but got {word_type_counts:?}");
+    }
+
+    let response = client.get_empty("/languages/Klingon/word-types").await?;
+    assert_response_status!(response, StatusCode::NOT_FOUND)
+}
+
+/// The word counts come from the materialized view, which `seed.sql` refreshes after seeding.
+async fn test_languages() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+
+    let response = client.get_empty("/languages").await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let language_word_counts: Vec<LanguageWordCount> = response.json().await?;
+    // Other tests may add languages, so only the seeded ones are checked.
+    for (language, word_count) in [("English", 4), ("French", 2), ("Scots", 1)] {
+        let expected = LanguageWordCount {
+            language: language.to_owned(),
+            word_count,
+        };
+        if !language_word_counts.contains(&expected) {
+            bail!("Expected {expected:?} in the language list, but got {language_word_counts:?}");
+        }
+    }
+
+    Ok(())
+}
+
+async fn test_unknown_route() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+
+    let response = client.get_empty("/does/not/exist").await?;
+    assert_response_status!(response, StatusCode::NOT_FOUND)?;
+    assert_route_does_not_exist_body(response).await?;
+
+    let response = client.post_empty("/does/not/exist").await?;
+    assert_response_status!(response, StatusCode::NOT_FOUND)?;
+    assert_route_does_not_exist_body(response).await
+}
+
+async fn assert_route_does_not_exist_body(response: reqwest::Response) -> anyhow::Result<()> {
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .context("missing content type")?
+        .to_str()?
+        .to_owned();
+    if content_type != "application/json" {
+        bail!("expected content type application/json, but got {content_type}");
+    }
+
+    let error_response: ErrorResponse = response.json().await?;
+    if !error_response.error.contains("/does/not/exist") {
+        bail!("unexpected error message: {}", error_response.error);
+    }
+
+    Ok(())
+}
+
+async fn test_trailing_slash() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+
+    let response = client
+        .post(
+            "/accounts/create/",
+            CreateAccount {
+                username: "hedy".to_owned(),
+                password: "lamarr😀😀😀".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    let response = client
+        .post(
+            "/accounts/login/",
+            Login {
+                username: "hedy".to_owned(),
+                password: "lamarr😀😀😀".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let response = client.get_empty("/accounts/whoami/").await?;
+    assert_response_status!(response, StatusCode::OK)
+}
+
+/// Requires the dictionary data and frequency ranks from `seed.sql`.
+async fn test_common_words() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let word = |language: &str, word: &str, word_type: &str| WordIdentifier {
+        language: language.to_owned(),
+        word: word.to_owned(),
+        word_type: word_type.to_owned(),
+    };
+
+    let response = client
+        .get(
+            "/words/common",
+            ListCommonWords {
+                language: "English".to_owned(),
+                limit: 3,
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let common_words: Vec<WordIdentifier> = response.json().await?;
+    let expected = vec![
+        word("English", "house", "Noun"),
+        word("English", "run", "Noun"),
+        word("English", "run", "Verb"),
+    ];
+    if common_words != expected {
+        bail!("Expected common words {expected:?}, but got {common_words:?}");
+    }
+
+    // words without a rank are ordered alphabetically
+    let response = client
+        .get(
+            "/words/common",
+            ListCommonWords {
+                language: "French".to_owned(),
+                limit: 10,
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let common_words: Vec<WordIdentifier> = response.json().await?;
+    let expected = vec![
+        word("French", "courir", "Verb"),
+        word("French", "maison", "Noun"),
+    ];
+    if common_words != expected {
+        bail!("Expected common words {expected:?}, but got {common_words:?}");
+    }
+
+    let response = client
+        .get(
+            "/words/common",
+            ListCommonWords {
+                language: "English".to_owned(),
+                limit: 1_000_000,
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::BAD_REQUEST)
+}
+
+/// The `Json` extractor of axum rejects requests without a JSON content type with `415 Unsupported Media Type`.
+/// Our own `Json` wrapper passes this rejection through unchanged.
+async fn test_wrong_content_type() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+
+    let response = client
+        .post_with_header(
+            "/accounts/create",
+            CreateAccount {
+                username: "grace".to_owned(),
+                password: "hopper😀😀😀".to_owned().into(),
+            },
+            "Content-Type",
+            "text/plain",
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::UNSUPPORTED_MEDIA_TYPE)?;
+
+    // the account was not created
+    let response = client
+        .post(
+            "/accounts/login",
+            Login {
+                username: "grace".to_owned(),
+                password: "hopper😀😀😀".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::BAD_REQUEST)?;
+
+    let response = client
+        .post(
+            "/accounts/create",
+            CreateAccount {
+                username: "grace".to_owned(),
+                password: "hopper😀😀😀".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::CREATED)?;
+
+    let response = client
+        .post(
+            "/accounts/login",
+            Login {
+                username: "grace".to_owned(),
+                password: "hopper😀😀😀".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)?;
+
+    let word = WordIdentifier {
+        language: "English".to_owned(),
+        word: "run".to_owned(),
+        word_type: "Verb".to_owned(),
+    };
+
+    let response = client
+        .put_with_header(
+            "/words/note",
+            SetWordNote {
+                word: word.clone(),
+                note: "not json".to_owned(),
+            },
+            "Content-Type",
+            "text/plain",
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::UNSUPPORTED_MEDIA_TYPE)?;
+
+    // the note was not created
+    let response = client.get("/words/note", &word).await?;
+    assert_response_status!(response, StatusCode::NOT_FOUND)
+}
+
+async fn test_server_time() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+
+    let before = chrono::Utc::now();
+    let response = client.get_empty("/time").await?;
+    let after = chrono::Utc::now();
+    assert_response_status!(response, StatusCode::OK)?;
+    let server_time: ServerTime = response.json().await?;
+
+    // The backend runs on the same machine, so there is no clock skew except for rounding.
+    let tolerance = chrono::Duration::try_seconds(1).unwrap();
+    if server_time.time < before - tolerance || server_time.time > after + tolerance {
+        bail!("Server time {server_time:?} is not between {before} and {after}");
+    }
+
+    Ok(())
+}
+
+async fn test_capabilities() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+
+    // Available without logging in
+    let response = client.get_empty("/capabilities").await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let capabilities: Capabilities = response.json().await?;
+
+    // The integration tests run with the breach check disabled
+    if capabilities.compromised_password_check {
+        bail!("Breach check is reported as enabled: {capabilities:?}");
+    }
+    if capabilities.minimum_password_length > capabilities.maximum_password_length
+        || capabilities.minimum_username_length > capabilities.maximum_username_length
+    {
+        bail!("Inconsistent length limits: {capabilities:?}");
+    }
+
+    Ok(())
+}
+
+async fn test_malformed_json() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+
+    // missing fields
+    let response = client
+        .post("/accounts/create", serde_json::json!({}))
+        .await?;
+    assert_response_status!(response, StatusCode::BAD_REQUEST)?;
+
+    // wrong field types
+    let response = client
+        .post(
+            "/accounts/create",
+            serde_json::json!({"username": 5, "password": "mcclintock"}),
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::BAD_REQUEST)?;
+
+    // syntax error
+    let response = client
+        .post_raw(
+            "/accounts/create",
+            r#"{"username": "barbara", "password": "#,
+            "application/json",
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::BAD_REQUEST)?;
+
+    // the error message describes the problem
+    let response = client
+        .post(
+            "/accounts/create",
+            serde_json::json!({"username": "barbara"}),
+        )
+        .await?;
+    let message = response.text().await?;
+    if !message.contains("password") {
+        bail!("Error message does not mention the missing field: {message:?}");
+    }
+
+    // the account was not created
+    let response = client
+        .post(
+            "/accounts/login",
+            Login {
+                username: "barbara".to_owned(),
+                password: "mcclintock".to_owned().into(),
+            },
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::BAD_REQUEST)
+}
+
+/// Requires the dictionary data from `seed.sql`.
+async fn test_detect_language() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+
+    for (word, expected) in [
+        // more common in Scots than in English
+        ("green", vec!["Scots", "English"]),
+        // multiple word types in the same language
+        ("run", vec!["English"]),
+        ("maison", vec!["French"]),
+        ("xylophone", vec![]),
+    ] {
+        let response = client
+            .get(
+                "/words/detect-language",
+                DetectLanguage {
+                    word: word.to_owned(),
+                },
+            )
+            .await?;
+        assert_response_status!(response, StatusCode::OK)?;
+        let languages: Vec<String> = response.json().await?;
+        if languages != expected {
+            bail!("Expected languages {expected:?} for {word:?}, but got {languages:?}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Requires the dictionary data from `seed.sql`.
+async fn test_random_word() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let filter = |language: Option<&str>, word_type: Option<&str>| RandomWord {
+        language: language.map(ToOwned::to_owned),
+        word_type: word_type.map(ToOwned::to_owned),
+    };
+
+    // English has four words, so repeated calls should not always return the same one.
+    let mut english_words = HashSet::new();
+    for _ in 0..50 {
+        let response = client
+            .get("/words/random", filter(Some("English"), None))
+            .await?;
+        assert_response_status!(response, StatusCode::OK)?;
+        let word: WordIdentifier = response.json().await?;
+        if word.language != "English" {
+            bail!("Expected an English word, but got {word:?}");
+        }
+        english_words.insert((word.word, word.word_type));
+    }
+    if english_words.len() < 2 {
+        bail!("Expected varying random words, but got {english_words:?}");
+    }
+
+    let response = client
+        .get("/words/random", filter(Some("French"), Some("Verb")))
+        .await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let word: WordIdentifier = response.json().await?;
+    if word.word != "courir" {
+        bail!("Expected the only French verb, but got {word:?}");
+    }
+
+    let response = client.get("/words/random", filter(None, None)).await?;
+    assert_response_status!(response, StatusCode::OK)?;
+
+    let response = client
+        .get("/words/random", filter(Some("Scots"), Some("Noun")))
+        .await?;
+    assert_response_status!(response, StatusCode::NO_CONTENT)
+}
+
+/// Requires the dictionary data from `seed.sql`.
+async fn test_translations() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let to = |language: &str| ListTranslations {
+        to: language.to_owned(),
+    };
+    let word = |word: &str, word_type: &str| WordIdentifier {
+        language: "English".to_owned(),
+        word: word.to_owned(),
+        word_type: word_type.to_owned(),
+    };
+
+    for (path, expected) in [
+        (
+            "/words/French/maison/translations",
+            vec![word("house", "Noun")],
+        ),
+        (
+            "/words/French/courir/translations",
+            vec![word("run", "Verb")],
+        ),
+        // translations are directional
+        ("/words/English/house/translations", vec![]),
+        // words without translations
+        ("/words/French/xylophone/translations", vec![]),
+    ] {
+        let response = client.get(path, to("English")).await?;
+        assert_response_status!(response, StatusCode::OK)?;
+        let translations: Vec<WordIdentifier> = response.json().await?;
+        if translations != expected {
+            bail!("Expected translations {expected:?} for {path:?}, but got {translations:?}");
+        }
+    }
+
+    let response = client
+        .get("/words/French/maison/translations", to("Scots"))
+        .await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let translations: Vec<WordIdentifier> = response.json().await?;
+    if !translations.is_empty() {
+        bail!("Expected no translations into Scots, but got {translations:?}");
+    }
+
+    let response = client
+        .get("/words/Klingon/maison/translations", to("English"))
+        .await?;
+    assert_response_status!(response, StatusCode::NOT_FOUND)?;
+    let response = client
+        .get("/words/French/maison/translations", to("Klingon"))
+        .await?;
+    assert_response_status!(response, StatusCode::NOT_FOUND)
+}
+
+/// Requires the dictionary data from `seed.sql`.
+async fn test_lemmas() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let word = |language: &str, word: &str| LanguageWord {
+        language: language.to_owned(),
+        word: word.to_owned(),
+    };
+
+    for (language_word, expected) in [
+        (word("English", "running"), vec!["run"]),
+        (word("English", "houses"), vec!["house"]),
+        (word("French", "courant"), vec!["courir"]),
+        // forms are specific to their language
+        (word("French", "running"), vec![]),
+        // base forms are no inflected forms
+        (word("English", "run"), vec![]),
+    ] {
+        let response = client.get("/words/lemma", &language_word).await?;
+        assert_response_status!(response, StatusCode::OK)?;
+        let lemmas: Vec<String> = response.json().await?;
+        if lemmas != expected {
+            bail!("Expected lemmas {expected:?} for {language_word:?}, but got {lemmas:?}");
+        }
+    }
+
+    let response = client
+        .get("/words/lemma", word("Klingon", "running"))
+        .await?;
+    assert_response_status!(response, StatusCode::NOT_FOUND)
+}
+
+/// Requires the dictionary data from `seed.sql`.
+async fn test_words_exist() -> anyhow::Result<()> {
+    let client = HttpClient::new().await?;
+    let word = |language: &str, word: &str| LanguageWord {
+        language: language.to_owned(),
+        word: word.to_owned(),
+    };
+
+    let response = client
+        .post(
+            "/words/exists",
+            vec![
+                word("English", "run"),
+                word("English", "maison"),
+                word("French", "maison"),
+                word("Klingon", "run"),
+                word("English", "fly"),
+                word("English", "green"),
+            ],
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::OK)?;
+    let exists: Vec<bool> = response.json().await?;
+    let expected = vec![true, false, true, false, false, true];
+    if exists != expected {
+        bail!("Expected {expected:?}, but got {exists:?}");
+    }
+
+    let response = client
+        .post("/words/exists", vec![word("English", "run"); 1001])
+        .await?;
+    assert_response_status!(response, StatusCode::BAD_REQUEST)?;
+
+    let response = client
+        .post(
+            "/words/exists",
+            vec![word("English", "run"), word("English", &"a".repeat(201))],
+        )
+        .await?;
+    assert_response_status!(response, StatusCode::BAD_REQUEST)?;
+
+    let response = client
+        .post("/words/exists", vec![word(&"a".repeat(201), "run")])
+        .await?;
+    assert_response_status!(response, StatusCode::BAD_REQUEST)
+}
@@ -12,7 +12,16 @@ pub struct HttpClient {
 
 impl HttpClient {
     pub async fn new() -> anyhow::Result<Self> {
-        let client = ClientBuilder::new().cookie_store(true).build()?;
+        Self::with_client_builder(ClientBuilder::new()).await
+    }
+
+    /// Create a client that talks HTTP/2 without negotiating it first.
+    pub async fn new_http2() -> anyhow::Result<Self> {
+        Self::with_client_builder(ClientBuilder::new().http2_prior_knowledge()).await
+    }
+
+    async fn with_client_builder(client_builder: ClientBuilder) -> anyhow::Result<Self> {
+        let client = client_builder.cookie_store(true).build()?;
 
         for _ in 0..10 {
             match client.get(BASE_URL).send().await {
@@ -41,10 +50,107 @@ impl HttpClient {
             .await?)
     }
 
+    pub async fn post_with_header<T: Serialize>(
+        &self,
+        path: &str,
+        body: T,
+        header: &str,
+        value: &str,
+    ) -> anyhow::Result<Response> {
+        Ok(self
+            .client
+            .post(format!("{BASE_URL}{path}"))
+            .header(header, value)
+            .json(&body)
+            .send()
+            .await?)
+    }
+
+    /// Post a body that is not serialised, e.g. to send malformed JSON.
+    pub async fn post_raw(
+        &self,
+        path: &str,
+        body: &'static str,
+        content_type: &str,
+    ) -> anyhow::Result<Response> {
+        Ok(self
+            .client
+            .post(format!("{BASE_URL}{path}"))
+            .header("Content-Type", content_type)
+            .body(body)
+            .send()
+            .await?)
+    }
+
+    pub async fn put<T: Serialize>(&self, path: &str, body: T) -> anyhow::Result<Response> {
+        Ok(self
+            .client
+            .put(format!("{BASE_URL}{path}"))
+            .json(&body)
+            .send()
+            .await?)
+    }
+
+    pub async fn put_with_header<T: Serialize>(
+        &self,
+        path: &str,
+        body: T,
+        header: &str,
+        value: &str,
+    ) -> anyhow::Result<Response> {
+        Ok(self
+            .client
+            .put(format!("{BASE_URL}{path}"))
+            .header(header, value)
+            .json(&body)
+            .send()
+            .await?)
+    }
+
+    pub async fn get<T: Serialize>(&self, path: &str, query: T) -> anyhow::Result<Response> {
+        Ok(self
+            .client
+            .get(format!("{BASE_URL}{path}"))
+            .query(&query)
+            .send()
+            .await?)
+    }
+
+    pub async fn get_empty_with_header(
+        &self,
+        path: &str,
+        header: &str,
+        value: &str,
+    ) -> anyhow::Result<Response> {
+        Ok(self
+            .client
+            .get(format!("{BASE_URL}{path}"))
+            .header(header, value)
+            .send()
+            .await?)
+    }
+
+    pub async fn get_empty(&self, path: &str) -> anyhow::Result<Response> {
+        Ok(self.client.get(format!("{BASE_URL}{path}")).send().await?)
+    }
+
     pub async fn post_empty(&self, path: &str) -> anyhow::Result<Response> {
         Ok(self.client.post(format!("{BASE_URL}{path}")).send().await?)
     }
 
+    pub async fn delete_with_query<T: Serialize>(
+        &self,
+        path: &str,
+        query: T,
+    ) -> anyhow::Result<Response> {
+        Ok(self
+            .client
+            .delete(format!("{BASE_URL}{path}"))
+            .query(&query)
+            .send()
+            .await?)
+    }
+
     pub async fn delete(&self, path: &str) -> anyhow::Result<Response> {
         Ok(self
             .client
@@ -52,6 +158,20 @@ impl HttpClient {
             .send()
             .await?)
     }
+
+    pub async fn delete_with_header(
+        &self,
+        path: &str,
+        header: &str,
+        value: &str,
+    ) -> anyhow::Result<Response> {
+        Ok(self
+            .client
+            .delete(format!("{BASE_URL}{path}"))
+            .header(header, value)
+            .send()
+            .await?)
+    }
 }
 
 pub async fn assert_response_status(response: Response, status: StatusCode) -> anyhow::Result<()> {
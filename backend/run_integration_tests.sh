@@ -15,8 +15,15 @@ set -e
 echo "Clearing database"
 diesel migration redo --all --locked-schema
 
+echo "Seeding database"
+psql --dbname rvoc_dev -v ON_ERROR_STOP=1 -f integration-tests/seed.sql
+
 echo "Starting backend in background"
-RUST_BACKTRACE=1 debugBinary/bin/rvoc-backend web 2>&1 > >(tee rvoc-backend.log) &
+SHUTDOWN_TIMEOUT_SECONDS=5
+set -a
+source integration-tests/web-api.env
+set +a
+RUST_BACKTRACE=1 RVOC_INTEGRATION_TEST_MODE=true RVOC_SHUTDOWN_TIMEOUT=$SHUTDOWN_TIMEOUT_SECONDS debugBinary/bin/rvoc-backend web 2>&1 > >(tee rvoc-backend.log) &
 BACKEND_PID=$!
 
 set +e
@@ -27,6 +34,24 @@ sleep 5
 echo "Running integration tests"
 RUST_BACKTRACE=1 integrationTestsBinary/bin/integration-tests 2>&1 | tee integration-tests.log
 
-echo "Terminating backend"
+echo "Terminating backend while a slow request is in flight"
+curl --silent --output /dev/null http://localhost:8093/test/sleep/600 &
+SLOW_REQUEST_PID=$!
+sleep 1
+if ! kill -0 $SLOW_REQUEST_PID 2>/dev/null; then
+    echo "The slow request completed before the shutdown started"
+    exit 1
+fi
+
+SHUTDOWN_START=$(date +%s)
 kill -SIGINT $BACKEND_PID
 wait $BACKEND_PID
+SHUTDOWN_DURATION=$(($(date +%s) - SHUTDOWN_START))
+kill $SLOW_REQUEST_PID 2>/dev/null
+
+# Allow for some delay in the shutdown after the timeout.
+if [ $SHUTDOWN_DURATION -gt $((SHUTDOWN_TIMEOUT_SECONDS + 2)) ]; then
+    echo "Shutdown took $SHUTDOWN_DURATION seconds, but the shutdown timeout is $SHUTDOWN_TIMEOUT_SECONDS seconds"
+    exit 1
+fi
+echo "Shutdown took $SHUTDOWN_DURATION seconds with a shutdown timeout of $SHUTDOWN_TIMEOUT_SECONDS seconds"
@@ -0,0 +1,2 @@
+DROP INDEX sessions_username_created_at;
+ALTER TABLE sessions DROP COLUMN created_at;
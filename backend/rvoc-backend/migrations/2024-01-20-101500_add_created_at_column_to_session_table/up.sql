@@ -0,0 +1,2 @@
+ALTER TABLE sessions ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT now();
+CREATE INDEX sessions_username_created_at ON sessions (username, created_at);
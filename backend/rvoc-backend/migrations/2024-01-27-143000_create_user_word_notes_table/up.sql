@@ -0,0 +1,9 @@
+CREATE TABLE user_word_notes (
+	username TEXT NOT NULL REFERENCES users (name) ON DELETE CASCADE,
+	word TEXT NOT NULL,
+	word_type INTEGER NOT NULL,
+	language INTEGER NOT NULL,
+	note TEXT NOT NULL,
+	PRIMARY KEY (username, word, word_type, language),
+	FOREIGN KEY (word, word_type, language) REFERENCES words ON DELETE CASCADE
+);
@@ -0,0 +1 @@
+DROP MATERIALIZED VIEW language_word_counts;
@@ -0,0 +1,4 @@
+CREATE MATERIALIZED VIEW language_word_counts AS
+	SELECT language, count(*) AS word_count FROM words GROUP BY language;
+-- Required to refresh the view concurrently.
+CREATE UNIQUE INDEX language_word_counts_language ON language_word_counts (language);
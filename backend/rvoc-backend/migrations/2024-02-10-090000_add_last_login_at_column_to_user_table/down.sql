@@ -0,0 +1,2 @@
+DROP INDEX users_last_login_at;
+ALTER TABLE users DROP COLUMN last_login_at;
@@ -0,0 +1,3 @@
+-- Existing users count as having logged in at the time of the migration.
+ALTER TABLE users ADD COLUMN last_login_at TIMESTAMPTZ NOT NULL DEFAULT now();
+CREATE INDEX users_last_login_at ON users (last_login_at);
@@ -0,0 +1 @@
+DROP INDEX words_language_word_type;
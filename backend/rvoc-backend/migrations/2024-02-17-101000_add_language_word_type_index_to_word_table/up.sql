@@ -0,0 +1 @@
+CREATE INDEX words_language_word_type ON words (language, word_type);
@@ -0,0 +1,2 @@
+DROP INDEX words_language_frequency_rank;
+ALTER TABLE words DROP COLUMN frequency_rank;
@@ -0,0 +1,2 @@
+ALTER TABLE words ADD COLUMN frequency_rank INTEGER;
+CREATE INDEX words_language_frequency_rank ON words (language, frequency_rank);
@@ -0,0 +1,10 @@
+CREATE TABLE api_tokens (
+	id SERIAL PRIMARY KEY,
+	username TEXT NOT NULL REFERENCES users(name) ON DELETE CASCADE,
+	-- SHA-256 of the token, the token itself is only shown once to the user.
+	token_hash TEXT NOT NULL UNIQUE,
+	label TEXT NOT NULL,
+	created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+	expiry TIMESTAMPTZ
+);
+CREATE INDEX api_tokens_username ON api_tokens (username);
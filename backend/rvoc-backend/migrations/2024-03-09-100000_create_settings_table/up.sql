@@ -0,0 +1,5 @@
+-- Values that the application stores for itself, as opposed to user data.
+CREATE TABLE settings (
+	name TEXT PRIMARY KEY,
+	value TEXT NOT NULL
+);
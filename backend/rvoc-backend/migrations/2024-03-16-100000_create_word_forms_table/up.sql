@@ -0,0 +1,8 @@
+-- Links inflected forms to their base forms, e.g. "running" to "run".
+-- A form may belong to several lemmas, and the lemmas are not required to be words of a specific word type.
+CREATE TABLE word_forms (
+	language INTEGER NOT NULL REFERENCES languages(id) ON DELETE CASCADE,
+	form TEXT NOT NULL,
+	lemma TEXT NOT NULL,
+	PRIMARY KEY (language, form, lemma)
+);
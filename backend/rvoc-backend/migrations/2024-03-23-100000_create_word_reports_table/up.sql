@@ -0,0 +1,13 @@
+-- Problems with the dictionary data reported by users, e.g. a wrong word type or a missing word.
+-- The reported word is stored by name, since it may not exist in the words table.
+CREATE TABLE word_reports (
+	id SERIAL PRIMARY KEY,
+	username TEXT NOT NULL REFERENCES users(name) ON DELETE CASCADE,
+	language TEXT NOT NULL,
+	word TEXT NOT NULL,
+	word_type TEXT NOT NULL,
+	reason TEXT NOT NULL,
+	created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+	resolved_at TIMESTAMPTZ
+);
+CREATE INDEX word_reports_username ON word_reports (username);
@@ -0,0 +1,13 @@
+-- Links a word to a word with the same meaning in another language.
+-- Translations are directional, like the translation tables in wiktionary, which the import does not provide yet.
+CREATE TABLE translations (
+	word TEXT NOT NULL,
+	word_type INTEGER NOT NULL,
+	language INTEGER NOT NULL,
+	translation_word TEXT NOT NULL,
+	translation_word_type INTEGER NOT NULL,
+	translation_language INTEGER NOT NULL,
+	PRIMARY KEY (language, word, word_type, translation_language, translation_word, translation_word_type),
+	FOREIGN KEY (word, word_type, language) REFERENCES words(word, word_type, language) ON DELETE CASCADE,
+	FOREIGN KEY (translation_word, translation_word_type, translation_language) REFERENCES words(word, word_type, language) ON DELETE CASCADE
+);
@@ -0,0 +1 @@
+ALTER TABLE job_queue DROP CONSTRAINT job_queue_name_check;
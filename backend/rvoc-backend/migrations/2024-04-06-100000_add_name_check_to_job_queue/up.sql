@@ -0,0 +1,6 @@
+-- Jobs with unknown names are deleted by the application anyways.
+DELETE FROM job_queue
+	WHERE name NOT IN ('UpdateWiktionary', 'DeleteExpiredSessions', 'RefreshWordStats', 'ExpireInactiveAccounts');
+
+ALTER TABLE job_queue ADD CONSTRAINT job_queue_name_check
+	CHECK (name IN ('UpdateWiktionary', 'DeleteExpiredSessions', 'RefreshWordStats', 'ExpireInactiveAccounts'));
@@ -0,0 +1,2 @@
+-- Set when an account is expired because of inactivity, and cleared when it is reactivated.
+ALTER TABLE users ADD COLUMN expired_at TIMESTAMPTZ;
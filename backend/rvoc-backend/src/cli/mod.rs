@@ -1,21 +1,35 @@
-use std::sync::{atomic, Arc};
+use std::{
+    path::PathBuf,
+    sync::{atomic, Arc},
+};
 
 use clap::Parser;
 use diesel_async::RunQueryDsl;
 use secure_string::{SecureBytes, SecureString};
 use tokio::io::{stdin, AsyncReadExt};
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 
 use crate::{
     configuration::Configuration,
     database::{
         create_async_database_connection_pool,
+        frequency_ranks::import_frequency_list,
         migrations::{has_missing_migrations, run_migrations},
+        password_pepper_canary::check_password_pepper_canary,
+        seed::seed_default_languages,
+        word_reports::{list_word_reports, resolve_word_report},
     },
     error::RVocError,
     error::RVocResult,
     integration_tests::run_internal_integration_tests,
-    job_queue::{jobs::update_witkionary::run_update_wiktionary, spawn_job_queue_runner},
+    job_queue::{
+        jobs::{
+            expire_inactive_accounts::reactivate_account,
+            refresh_word_stats::refresh_word_stats,
+            update_witkionary::{run_update_wiktionary, run_update_wiktionary_from_file},
+        },
+        spawn_job_queue_runner,
+    },
     model::user::password_hash::PasswordHash,
     web::run_web_api,
 };
@@ -31,8 +45,12 @@ enum Cli {
     /// This is done automatically while the web application runs.
     /// But if required, it can be run manually with this command.
     ///
-    /// **WARNING:** The web API should not run while running this command, since otherwise a simultaneous update is possible, with unforseeable results.
-    UpdateWiktionary,
+    /// If an update is already in progress, e.g. by the web API, then this command fails immediately.
+    UpdateWiktionary {
+        /// Import this local dump file instead of downloading the newest dump.
+        #[arg(long)]
+        from_file: Option<PathBuf>,
+    },
 
     /// Apply pending database migrations.
     ApplyMigrations,
@@ -45,6 +63,14 @@ enum Cli {
     /// This should always succeed, and sessions that are updated simultaneously should be be logged out anyways.
     ExpireAllSessions,
 
+    /// Insert the configured default languages if they do not exist yet.
+    /// This is done automatically when starting the web API.
+    SeedDefaults,
+
+    /// Refresh the precomputed word statistics.
+    /// This is done automatically while the web application runs.
+    RefreshStats,
+
     /// Set the password of a user.
     /// If no password is given, then it is read from stdin.
     SetPassword {
@@ -57,6 +83,40 @@ enum Cli {
         password: Option<SecureBytes>,
     },
 
+    /// Reactivate an account that was expired because of inactivity.
+    /// The user can log in with their old password again.
+    ReactivateAccount {
+        /// The name of the user.
+        #[arg(short, long)]
+        username: String,
+    },
+
+    /// Set the frequency ranks of the words of a language from a CSV file.
+    /// Each line of the file has the form `word,rank`, where a lower rank means a more common word.
+    /// Words that do not exist in the database are ignored.
+    ImportFrequencyList {
+        /// The english name of the language of the words.
+        #[arg(short, long)]
+        language: String,
+        /// The CSV file to import.
+        #[arg(short, long)]
+        file: PathBuf,
+    },
+
+    /// List the problems with the dictionary data reported by users, oldest first.
+    ListWordReports {
+        /// Also list reports that are resolved already.
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Mark a word report as resolved, e.g. after fixing the reported problem.
+    ResolveWordReport {
+        /// The id of the report, as shown by `list-word-reports`.
+        #[arg(long)]
+        id: i32,
+    },
+
     /// Run integration tests that require a database, but use APIs that are not exposed through the web interface.
     RunInternalIntegrationTests,
 }
@@ -68,19 +128,79 @@ pub async fn run_cli_command(configuration: &Configuration) -> RVocResult<()> {
 
     match cli_command {
         Cli::Web => run_rvoc_backend(configuration).await?,
-        Cli::UpdateWiktionary => {
+        Cli::UpdateWiktionary { from_file: None } => {
             run_update_wiktionary(
                 &create_async_database_connection_pool(configuration).await?,
                 configuration,
             )
             .await?
         }
+        Cli::UpdateWiktionary {
+            from_file: Some(dump_file),
+        } => {
+            run_update_wiktionary_from_file(
+                dump_file,
+                &create_async_database_connection_pool(configuration).await?,
+                configuration,
+            )
+            .await?
+        }
         Cli::ApplyMigrations => apply_pending_database_migrations(configuration).await?,
         Cli::ExpireAllPasswords => expire_all_passwords(configuration).await?,
         Cli::ExpireAllSessions => expire_all_sessions(configuration).await?,
+        Cli::SeedDefaults => {
+            seed_default_languages(
+                &create_async_database_connection_pool(configuration).await?,
+                configuration,
+            )
+            .await?
+        }
+        Cli::RefreshStats => {
+            refresh_word_stats(
+                &create_async_database_connection_pool(configuration).await?,
+                configuration,
+            )
+            .await?
+        }
         Cli::SetPassword { username, password } => {
             set_password(username, password, configuration).await?
         }
+        Cli::ReactivateAccount { username } => {
+            reactivate_account(
+                &username,
+                &create_async_database_connection_pool(configuration).await?,
+                configuration,
+            )
+            .await?
+        }
+        Cli::ImportFrequencyList { language, file } => {
+            import_frequency_list(
+                &language,
+                &file,
+                &create_async_database_connection_pool(configuration).await?,
+                configuration,
+            )
+            .await?;
+        }
+        Cli::ListWordReports { all } => {
+            for word_report in list_word_reports(
+                all,
+                &create_async_database_connection_pool(configuration).await?,
+                configuration,
+            )
+            .await?
+            {
+                println!("{word_report}");
+            }
+        }
+        Cli::ResolveWordReport { id } => {
+            resolve_word_report(
+                id,
+                &create_async_database_connection_pool(configuration).await?,
+                configuration,
+            )
+            .await?
+        }
         Cli::RunInternalIntegrationTests => run_internal_integration_tests(configuration).await?,
     }
 
@@ -90,14 +210,25 @@ pub async fn run_cli_command(configuration: &Configuration) -> RVocResult<()> {
 #[instrument(err, skip(configuration))]
 async fn run_rvoc_backend(configuration: &Configuration) -> RVocResult<()> {
     debug!("Running rvoc backend with configuration: {configuration:#?}");
+    info!(
+        "Effective configuration: {:#?}",
+        configuration.redacted_summary()
+    );
+
+    if configuration.auto_migrate {
+        apply_pending_database_migrations(configuration).await?;
+    }
 
     let database_connection_pool = create_async_database_connection_pool(configuration).await?;
 
+    seed_default_languages(&database_connection_pool, configuration).await?;
+    check_password_pepper_canary(&database_connection_pool, configuration).await?;
+
     // Create shutdown flag.
     let do_shutdown = Arc::new(atomic::AtomicBool::new(false));
 
     // Start job queue
-    let job_queue_join_handle: tokio::task::JoinHandle<Result<(), RVocError>> =
+    let mut job_queue_join_handle: tokio::task::JoinHandle<Result<(), RVocError>> =
         spawn_job_queue_runner(
             database_connection_pool.clone(),
             do_shutdown.clone(),
@@ -106,18 +237,22 @@ async fn run_rvoc_backend(configuration: &Configuration) -> RVocResult<()> {
         .await?;
 
     // Start web API
-    run_web_api(database_connection_pool, configuration).await?;
+    let shutdown_deadline = run_web_api(database_connection_pool, configuration).await?;
 
     // Shutdown
     info!("Shutting down...");
     do_shutdown.store(true, atomic::Ordering::Relaxed);
 
     info!("Waiting for asynchronous tasks to finish...");
-    job_queue_join_handle
-        .await
-        .map_err(|error| RVocError::TokioTaskJoin {
+    match tokio::time::timeout_at(shutdown_deadline, &mut job_queue_join_handle).await {
+        Ok(result) => result.map_err(|error| RVocError::TokioTaskJoin {
             source: Box::new(error),
-        })??;
+        })??,
+        Err(_) => {
+            warn!("Job queue did not finish within the shutdown timeout, aborting it");
+            job_queue_join_handle.abort();
+        }
+    }
 
     Ok(())
 }
@@ -1,8 +1,24 @@
-use std::{env::VarError, error::Error, net::SocketAddr, path::PathBuf, str::FromStr};
+use std::{
+    env::VarError,
+    error::Error,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use crate::error::{RVocError, RVocResult, UserError};
+use crate::job_queue::JobName;
 use chrono::Duration;
 use secure_string::{SecureBytes, SecureString};
+use tracing::warn;
+
+pub mod summary;
+
+/// Batch sizes above this bound are accepted, but a warning is logged.
+const LARGE_WIKTIONARY_DUMP_INSERTION_BATCH_SIZE: usize = 100_000;
+
+/// The file in which the generated password pepper is stored in dev mode, relative to the working directory.
+pub const DEV_PASSWORD_PEPPER_FILE: &str = ".rvoc-dev-password-pepper";
 
 /// The configuration of the application.
 #[derive(Debug, Clone)]
@@ -10,15 +26,45 @@ pub struct Configuration {
     /// If set, then some features that are unsuitable for integration tests are disabled.
     /// For example:
     ///  * Do not run the wiktionary update.
+    ///
+    /// Which jobs are disabled is decided by [`Configuration::should_run_job`].
     pub integration_test_mode: bool,
 
+    /// If set, then missing secrets that are required in production are generated for local development.
+    /// For example:
+    ///  * If `PASSWORD_PEPPER` is unset, a random pepper is generated and persisted in [`DEV_PASSWORD_PEPPER_FILE`].
+    pub dev_mode: bool,
+
     /// The url to access postgres.
     pub postgres_url: SecureString,
 
+    /// If set, then pending database migrations are applied automatically when starting the web API.
+    ///
+    /// **Warning:** Only enable this if there is a single instance of the backend.
+    /// If multiple instances start at the same time, they may try to run the migrations concurrently,
+    /// with unforseeable results.
+    pub auto_migrate: bool,
+
+    /// If set, pooled database connections are checked with a round trip to the database before being reused.
+    /// This costs a query per transaction, but prevents failures when the database or a load balancer
+    /// has silently closed an idle connection.
+    pub database_verify_connections: bool,
+
+    /// If set, pooled database connections older than this are closed instead of being reused.
+    pub database_maximum_connection_lifetime: Option<Duration>,
+
+    /// If set, postgres aborts statements that run longer than this.
+    pub database_statement_timeout: Option<Duration>,
+
     /// The url to send opentelemetry to.
     pub opentelemetry_url: Option<String>,
 
     /// The amount of time to wait for processes to shutdown gracefully.
+    /// After receiving a shutdown signal, in-flight requests and the job queue get this long to finish,
+    /// then they are aborted.
+    ///
+    /// To test this manually, send SIGINT while a slow request (e.g. a login with expensive password hashing parameters)
+    /// is in progress, and check that the process exits within the timeout.
     pub shutdown_timeout: Duration,
 
     /// The interval at which the job queue will be polled.
@@ -27,9 +73,61 @@ pub struct Configuration {
     /// The maximum number of retries for a failed transaction.
     pub maximum_transaction_retry_count: u64,
 
+    /// The delay before the first retry of a failed transaction.
+    /// The delay doubles with each further retry, and a random jitter of up to half the delay is subtracted,
+    /// such that conflicting transactions do not retry in lockstep.
+    pub transaction_retry_base_delay: Duration,
+
+    /// The maximum delay between retries of a failed transaction, before applying jitter.
+    pub transaction_retry_max_delay: Duration,
+
     /// The address to listen for API requests.
     pub api_listen_address: SocketAddr,
 
+    /// If set, the API is served via HTTP/2 in addition to HTTP/1.
+    /// HTTP/2 connections are detected automatically, there is no need for a separate port.
+    pub api_http2_enabled: bool,
+
+    /// If set, HTTP/1 connections are kept alive after a request has been answered.
+    pub api_http1_keep_alive: bool,
+
+    /// The interval at which HTTP/2 ping frames are sent to keep idle connections alive.
+    /// If unset, no ping frames are sent.
+    /// This should be lower than the idle timeout of any load balancer in front of the API, e.g. 30 seconds.
+    pub api_http2_keep_alive_interval: Option<Duration>,
+
+    /// The time to wait for the acknowledgement of an HTTP/2 ping frame before closing the connection.
+    pub api_http2_keep_alive_timeout: Duration,
+
+    /// The maximum number of concurrent streams a client may open in a single HTTP/2 connection.
+    pub api_http2_max_concurrent_streams: u32,
+
+    /// The time a TCP connection needs to be idle before TCP keep-alive probes are sent.
+    /// If unset, TCP keep-alive is disabled.
+    pub api_tcp_keep_alive: Option<Duration>,
+
+    /// If set, trailing slashes are removed from request paths before routing,
+    /// such that e.g. `/accounts/login/` is handled like `/accounts/login`.
+    pub api_trim_trailing_slashes: bool,
+
+    /// The origins that are allowed to make state-changing requests, e.g. `https://example.com`.
+    /// If a state-changing request carries an `Origin` or `Referer` header with an origin not in this list,
+    /// it is rejected. Requests without these headers are always allowed.
+    /// If empty, no origins are checked.
+    pub api_allowed_origins: Vec<String>,
+
+    /// The value of the `Content-Security-Policy` header that is sent with every response.
+    pub content_security_policy: String,
+
+    /// The value of the `Strict-Transport-Security` header that is sent with every response.
+    /// This should only be set if the API is served via TLS, e.g. by a reverse proxy.
+    /// If unset, the header is not sent.
+    pub strict_transport_security: Option<String>,
+
+    /// If set, the session cookie is sent with the `Secure` attribute, so browsers only send it via HTTPS.
+    /// This should only be unset for local development without TLS.
+    pub cookie_secure: bool,
+
     /// The minimum length of a username.
     pub minimum_username_length: usize,
 
@@ -46,9 +144,29 @@ pub struct Configuration {
     /// for how to set this if you want to set it manually.
     pub maximum_password_length: usize,
 
+    /// If set, new passwords are checked against the Have I Been Pwned database of breached passwords.
+    pub hibp_check_enabled: bool,
+
+    /// The url of the Have I Been Pwned range API, up to and including the slash before the hash prefix.
+    pub hibp_api_url: String,
+
+    /// The timeout for requests to the Have I Been Pwned API.
+    /// If it is exceeded, the API is treated as unavailable.
+    pub hibp_timeout: Duration,
+
+    /// The maximum number of breach checks per minute that the password rating endpoint may trigger, shared between all clients.
+    /// The endpoint does not require authentication, so without a limit it could be used to flood the Have I Been Pwned API.
+    /// Ratings beyond this limit report the breach check as unavailable, and zero disables the breach check for ratings.
+    pub maximum_password_rating_breach_checks_per_minute: u32,
+
     /// An additional salt that is shared between all passwords, but not stored in the database.
     pub password_pepper: SecureBytes,
 
+    /// Peppers that were used previously, to allow rotating the pepper.
+    /// Passwords hashed with one of these are still accepted, and rehashed with the current pepper on login.
+    /// Given as a comma-separated list, so the peppers cannot contain commas.
+    pub previous_password_peppers: Vec<SecureBytes>,
+
     /// The minimum memory parameter of the Argon2id password hash function.
     /// See the [OWASP password storage cheat sheet](https://cheatsheetseries.owasp.org/cheatsheets/Password_Storage_Cheat_Sheet.html#argon2id)
     /// for how to set this if you want to set it manually.
@@ -69,6 +187,54 @@ pub struct Configuration {
     /// If more tries happen than this number, the request will fail.
     pub maximum_session_id_generation_retry_count: u32,
 
+    /// The time after which a session expires if it is not used.
+    /// Each renewal extends the session by this time, so a session that is used regularly stays valid.
+    /// The default of 30 days keeps users of a learning app logged in between occasional practice sessions.
+    pub session_time_to_live: Duration,
+
+    /// The minimum time between two renewals of a session.
+    /// Renewing a session extends its expiry and rotates its id, which requires a database write.
+    /// Requests within this interval since the last renewal leave the session untouched,
+    /// which reduces the write load caused by clients sending many requests.
+    pub session_minimum_renewal_interval: Duration,
+
+    /// The maximum number of sessions a single user can be logged in with at the same time.
+    /// If a user logs in with more sessions, then the least recently used ones are deleted.
+    pub maximum_sessions_per_user: usize,
+
+    /// The maximum number of sessions deleted per transaction when deleting an account.
+    /// The sessions are deleted in batches before the user itself, to avoid a single huge transaction.
+    pub session_delete_batch_size: usize,
+
+    /// The maximum length of the label of an api token.
+    pub maximum_api_token_label_length: usize,
+
+    /// The maximum length of a note that a user can attach to a word.
+    pub maximum_word_note_length: usize,
+
+    /// The maximum number of words a single user can attach notes to.
+    pub maximum_word_notes_per_user: usize,
+
+    /// The maximum length of the reason given when reporting a problem with a word.
+    pub maximum_word_report_reason_length: usize,
+
+    /// The maximum number of unresolved word reports of a single user.
+    pub maximum_open_word_reports_per_user: usize,
+
+    /// The maximum number of words that can be requested from the common words list at once.
+    pub maximum_common_words_limit: usize,
+
+    /// The maximum number of words in a single batch request.
+    pub maximum_word_batch_size: usize,
+
+    /// The maximum length of a word or language name in a batch request.
+    /// Requests with longer strings are rejected, since no such word can exist.
+    /// Longer words in the wiktionary dump are skipped during import.
+    pub maximum_word_length: usize,
+
+    /// The english names of the languages that are inserted into the database on startup, if they do not exist yet.
+    pub default_languages: Vec<String>,
+
     /// The base directory where wiktionary dumps are stored in.
     pub wiktionary_temporary_data_directory: PathBuf,
 
@@ -78,23 +244,72 @@ pub struct Configuration {
     /// The interval at which wiktionary is polled for new dumps, and the dumps are integrated if there is a new one.
     pub wiktionary_update_interval: Duration,
 
+    /// How often a scheduled wiktionary update is retried after a transient failure, like a network error,
+    /// before giving up until the next scheduled update.
+    pub maximum_wiktionary_update_retry_count: u64,
+
+    /// The delay before the first retry of a failed wiktionary update.
+    /// The delay doubles with each further retry.
+    pub wiktionary_update_retry_delay: Duration,
+
     /// The interval at which expired sessions are deleted from the database.
     pub delete_expired_sessions_interval: Duration,
+
+    /// The interval at which the materialized word statistics are refreshed.
+    pub refresh_word_stats_interval: Duration,
+
+    /// If set, the accounts of users who have not logged in for this long are expired, and their sessions are deleted.
+    /// Expired accounts cannot log in until they are reactivated with the `reactivate-account` command.
+    pub account_inactivity_expiry: Option<Duration>,
+
+    /// The interval at which inactive accounts are expired.
+    pub expire_inactive_accounts_interval: Duration,
 }
 
 impl Configuration {
     /// Read the configuration values from environment variables.
+    ///
+    /// The result is not validated yet, see [`Configuration::validate`].
     pub fn from_environment() -> RVocResult<Self> {
+        let dev_mode = read_env_var_with_default_as_type("RVOC_DEV_MODE", false)?;
+        let api_http1_keep_alive = read_env_var_with_default_as_type("API_HTTP1_KEEP_ALIVE", true)?;
+        let api_http2_keep_alive_timeout = Duration::try_seconds(
+            read_env_var_with_default_as_type("API_HTTP2_KEEP_ALIVE_TIMEOUT_SECONDS", 20i64)?,
+        )
+        .unwrap();
+        let api_tcp_keep_alive =
+            read_optional_env_var_as_type::<i64>("API_TCP_KEEP_ALIVE_SECONDS")?
+                .map(|seconds| Duration::try_seconds(seconds).unwrap());
+        let previous_password_peppers = read_env_var_with_default("PREVIOUS_PASSWORD_PEPPERS", "")?
+            .split(',')
+            .filter(|pepper| !pepper.is_empty())
+            .map(SecureBytes::from)
+            .collect();
+
         let result =
             Self {
                 integration_test_mode: read_env_var_with_default_as_type(
                     "RVOC_INTEGRATION_TEST_MODE",
                     false,
                 )?,
+                dev_mode,
                 postgres_url: read_env_var_with_default_as_type(
                     "POSTGRES_RVOC_URL",
                     "postgres://rvoc@localhost/rvoc",
                 )?,
+                auto_migrate: read_env_var_with_default_as_type("RVOC_AUTO_MIGRATE", false)?,
+                database_verify_connections: read_env_var_with_default_as_type(
+                    "DATABASE_VERIFY_CONNECTIONS",
+                    true,
+                )?,
+                database_maximum_connection_lifetime: read_optional_env_var_as_type::<i64>(
+                    "DATABASE_MAXIMUM_CONNECTION_LIFETIME_SECONDS",
+                )?
+                .map(|seconds| Duration::try_seconds(seconds).unwrap()),
+                database_statement_timeout: read_optional_env_var_as_type::<i64>(
+                    "DATABASE_STATEMENT_TIMEOUT_MILLISECONDS",
+                )?
+                .map(|milliseconds| Duration::try_milliseconds(milliseconds).unwrap()),
                 opentelemetry_url: read_optional_env_var("OPENTELEMETRY_URL")?,
                 shutdown_timeout: Duration::try_seconds(read_env_var_with_default_as_type(
                     "RVOC_SHUTDOWN_TIMEOUT",
@@ -110,10 +325,52 @@ impl Configuration {
                     "MAXIMUM_TRANSACTION_RETRY_COUNT",
                     10u64,
                 )?,
+                transaction_retry_base_delay: Duration::try_milliseconds(
+                    read_env_var_with_default_as_type(
+                        "TRANSACTION_RETRY_BASE_DELAY_MILLISECONDS",
+                        10i64,
+                    )?,
+                )
+                .unwrap(),
+                transaction_retry_max_delay: Duration::try_milliseconds(
+                    read_env_var_with_default_as_type(
+                        "TRANSACTION_RETRY_MAX_DELAY_MILLISECONDS",
+                        1000i64,
+                    )?,
+                )
+                .unwrap(),
                 api_listen_address: read_env_var_with_default_as_type(
                     "API_LISTEN_ADDRESS",
                     SocketAddr::from(([0, 0, 0, 0], 8093)),
                 )?,
+                api_http2_enabled: read_env_var_with_default_as_type("API_HTTP2_ENABLED", true)?,
+                api_http1_keep_alive,
+                api_http2_keep_alive_interval: read_optional_env_var_as_type::<i64>(
+                    "API_HTTP2_KEEP_ALIVE_INTERVAL_SECONDS",
+                )?
+                .map(|seconds| Duration::try_seconds(seconds).unwrap()),
+                api_http2_keep_alive_timeout,
+                api_http2_max_concurrent_streams: read_env_var_with_default_as_type(
+                    "API_HTTP2_MAX_CONCURRENT_STREAMS",
+                    200u32,
+                )?,
+                api_tcp_keep_alive,
+                api_trim_trailing_slashes: read_env_var_with_default_as_type(
+                    "API_TRIM_TRAILING_SLASHES",
+                    true,
+                )?,
+                api_allowed_origins: read_env_var_with_default("API_ALLOWED_ORIGINS", "")?
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|origin| !origin.is_empty())
+                    .map(ToOwned::to_owned)
+                    .collect(),
+                content_security_policy: read_env_var_with_default(
+                    "CONTENT_SECURITY_POLICY",
+                    "default-src 'none'; frame-ancestors 'none'",
+                )?,
+                strict_transport_security: read_optional_env_var("STRICT_TRANSPORT_SECURITY")?,
+                cookie_secure: read_env_var_with_default_as_type("COOKIE_SECURE", true)?,
                 minimum_username_length: read_env_var_with_default_as_type(
                     "MINIMUM_USERNAME_LENGTH",
                     3usize,
@@ -130,7 +387,27 @@ impl Configuration {
                     "MAXIMUM_PASSWORD_LENGTH",
                     100usize,
                 )?,
-                password_pepper: read_env_var_as_type("PASSWORD_PEPPER")?,
+                hibp_check_enabled: read_env_var_with_default_as_type("HIBP_CHECK_ENABLED", false)?,
+                hibp_api_url: read_env_var_with_default(
+                    "HIBP_API_URL",
+                    "https://api.pwnedpasswords.com/range/",
+                )?,
+                hibp_timeout: Duration::try_seconds(read_env_var_with_default_as_type(
+                    "HIBP_TIMEOUT_SECONDS",
+                    5i64,
+                )?)
+                .unwrap(),
+                maximum_password_rating_breach_checks_per_minute:
+                    read_env_var_with_default_as_type(
+                        "MAXIMUM_PASSWORD_RATING_BREACH_CHECKS_PER_MINUTE",
+                        60u32,
+                    )?,
+                password_pepper: resolve_password_pepper(
+                    read_optional_env_var_as_type("PASSWORD_PEPPER")?,
+                    dev_mode,
+                    Path::new(DEV_PASSWORD_PEPPER_FILE),
+                )?,
+                previous_password_peppers,
                 password_argon2id_minimum_memory_kib: read_env_var_with_default_as_type(
                     "PASSWORD_ARGON2ID_MINIMUM_MEMORY_KIB",
                     19456u32,
@@ -147,6 +424,64 @@ impl Configuration {
                     "MAXIMUM_SESSION_ID_GENERATION_RETRY_COUNT",
                     10u32,
                 )?,
+                session_time_to_live: Duration::try_hours(read_env_var_with_default_as_type(
+                    "SESSION_TIME_TO_LIVE_HOURS",
+                    720i64,
+                )?)
+                .unwrap(),
+                session_minimum_renewal_interval: Duration::try_seconds(
+                    read_env_var_with_default_as_type(
+                        "SESSION_MINIMUM_RENEWAL_INTERVAL_SECONDS",
+                        300i64,
+                    )?,
+                )
+                .unwrap(),
+                maximum_sessions_per_user: read_env_var_with_default_as_type(
+                    "MAXIMUM_SESSIONS_PER_USER",
+                    20usize,
+                )?,
+                session_delete_batch_size: read_env_var_with_default_as_type(
+                    "SESSION_DELETE_BATCH_SIZE",
+                    1000usize,
+                )?,
+                maximum_api_token_label_length: read_env_var_with_default_as_type(
+                    "MAXIMUM_API_TOKEN_LABEL_LENGTH",
+                    100usize,
+                )?,
+                maximum_word_note_length: read_env_var_with_default_as_type(
+                    "MAXIMUM_WORD_NOTE_LENGTH",
+                    2000usize,
+                )?,
+                maximum_word_notes_per_user: read_env_var_with_default_as_type(
+                    "MAXIMUM_WORD_NOTES_PER_USER",
+                    100_000usize,
+                )?,
+                maximum_word_report_reason_length: read_env_var_with_default_as_type(
+                    "MAXIMUM_WORD_REPORT_REASON_LENGTH",
+                    1000usize,
+                )?,
+                maximum_open_word_reports_per_user: read_env_var_with_default_as_type(
+                    "MAXIMUM_OPEN_WORD_REPORTS_PER_USER",
+                    20usize,
+                )?,
+                maximum_common_words_limit: read_env_var_with_default_as_type(
+                    "MAXIMUM_COMMON_WORDS_LIMIT",
+                    1000usize,
+                )?,
+                maximum_word_batch_size: read_env_var_with_default_as_type(
+                    "MAXIMUM_WORD_BATCH_SIZE",
+                    1000usize,
+                )?,
+                maximum_word_length: read_env_var_with_default_as_type(
+                    "MAXIMUM_WORD_LENGTH",
+                    200usize,
+                )?,
+                default_languages: read_env_var_with_default("DEFAULT_LANGUAGES", "")?
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|language| !language.is_empty())
+                    .map(ToOwned::to_owned)
+                    .collect(),
                 wiktionary_temporary_data_directory: read_env_var_with_default_as_type(
                     "WIKTIONARY_TEMPORARY_DATA_DIRECTORY",
                     "data/wiktionary_data",
@@ -159,6 +494,17 @@ impl Configuration {
                     read_env_var_with_default_as_type::<i64>("WIKTIONARY_POLL_INTERVAL_HOURS", 24)?,
                 )
                 .unwrap(),
+                maximum_wiktionary_update_retry_count: read_env_var_with_default_as_type(
+                    "MAXIMUM_WIKTIONARY_UPDATE_RETRY_COUNT",
+                    3u64,
+                )?,
+                wiktionary_update_retry_delay: Duration::try_minutes(
+                    read_env_var_with_default_as_type::<i64>(
+                        "WIKTIONARY_UPDATE_RETRY_DELAY_MINUTES",
+                        5,
+                    )?,
+                )
+                .unwrap(),
                 delete_expired_sessions_interval: Duration::try_hours(
                     read_env_var_with_default_as_type::<i64>(
                         "DELETE_EXPIRED_SESSIONS_INTERVAL_HOURS",
@@ -166,65 +512,222 @@ impl Configuration {
                     )?,
                 )
                 .unwrap(),
+                refresh_word_stats_interval: Duration::try_minutes(
+                    read_env_var_with_default_as_type::<i64>(
+                        "REFRESH_WORD_STATS_INTERVAL_MINUTES",
+                        60,
+                    )?,
+                )
+                .unwrap(),
+                account_inactivity_expiry: read_optional_env_var_as_type::<i64>(
+                    "ACCOUNT_INACTIVITY_EXPIRY_DAYS",
+                )?
+                .map(|days| Duration::try_days(days).unwrap()),
+                expire_inactive_accounts_interval: Duration::try_hours(
+                    read_env_var_with_default_as_type::<i64>(
+                        "EXPIRE_INACTIVE_ACCOUNTS_INTERVAL_HOURS",
+                        24,
+                    )?,
+                )
+                .unwrap(),
             };
 
-        if result.shutdown_timeout < Duration::zero() {
+        Ok(result)
+    }
+
+    /// Check that the configuration values are consistent.
+    /// Values that are valid but likely unintended are logged as warnings,
+    /// so this should be called after setting up tracing.
+    pub fn validate(&self) -> RVocResult<()> {
+        if self.shutdown_timeout < Duration::zero() {
             return Err(RVocError::NegativeShutdownTimeout);
         }
 
-        if result.job_queue_poll_interval < Duration::zero() {
+        if self.job_queue_poll_interval < Duration::zero() {
             return Err(RVocError::NegativeJobQueuePollInterval);
         }
 
-        let password_pepper_length = result.password_pepper.unsecure().len();
+        if self.wiktionary_update_retry_delay < Duration::zero() {
+            return Err(RVocError::NegativeWiktionaryUpdateRetryDelay);
+        }
+
+        if self.transaction_retry_base_delay < Duration::zero()
+            || self.transaction_retry_max_delay < self.transaction_retry_base_delay
+        {
+            return Err(RVocError::TransactionRetryDelayBounds {
+                base_delay: self.transaction_retry_base_delay,
+                max_delay: self.transaction_retry_max_delay,
+            });
+        }
+
+        for (name, duration) in [
+            (
+                "API_HTTP2_KEEP_ALIVE_INTERVAL_SECONDS",
+                self.api_http2_keep_alive_interval,
+            ),
+            (
+                "API_HTTP2_KEEP_ALIVE_TIMEOUT_SECONDS",
+                Some(self.api_http2_keep_alive_timeout),
+            ),
+            ("API_TCP_KEEP_ALIVE_SECONDS", self.api_tcp_keep_alive),
+        ] {
+            if duration.is_some_and(|duration| duration <= Duration::zero()) {
+                return Err(RVocError::NonPositiveApiKeepAliveDuration { name });
+            }
+        }
+
+        if self.api_http2_max_concurrent_streams == 0 {
+            return Err(RVocError::ZeroApiHttp2MaxConcurrentStreams);
+        }
+
+        if self.session_minimum_renewal_interval < Duration::zero()
+            || self.session_minimum_renewal_interval >= self.session_time_to_live
+        {
+            return Err(RVocError::SessionRenewalIntervalBounds {
+                minimum_renewal_interval: self.session_minimum_renewal_interval,
+                time_to_live: self.session_time_to_live,
+            });
+        }
+
+        if self.maximum_sessions_per_user == 0 {
+            return Err(RVocError::ZeroMaximumSessionsPerUser);
+        }
+
+        if self.session_delete_batch_size == 0 {
+            return Err(RVocError::ZeroSessionDeleteBatchSize);
+        }
+
+        if self.hibp_timeout <= Duration::zero() {
+            return Err(RVocError::NonPositiveHibpTimeout);
+        }
+
         let password_pepper_min_length = 8;
         let password_pepper_max_length = 64;
 
-        if password_pepper_length < password_pepper_min_length
-            || password_pepper_length > password_pepper_max_length
+        for password_pepper in
+            std::iter::once(&self.password_pepper).chain(&self.previous_password_peppers)
         {
-            return Err(RVocError::PasswordPepperLength {
-                actual: password_pepper_length,
-                minimum: password_pepper_min_length,
-                maximum: password_pepper_max_length,
-            });
+            let password_pepper_length = password_pepper.unsecure().len();
+
+            if password_pepper_length < password_pepper_min_length
+                || password_pepper_length > password_pepper_max_length
+            {
+                return Err(RVocError::PasswordPepperLength {
+                    actual: password_pepper_length,
+                    minimum: password_pepper_min_length,
+                    maximum: password_pepper_max_length,
+                });
+            }
         }
 
         let minimum_password_length_minimum = 8;
-        if result.minimum_password_length < minimum_password_length_minimum {
+        if self.minimum_password_length < minimum_password_length_minimum {
             return Err(RVocError::MinimumPasswordLength {
-                actual: result.minimum_password_length,
+                actual: self.minimum_password_length,
                 minimum: minimum_password_length_minimum,
             });
         }
 
-        result.build_argon2_parameters()?;
+        if self
+            .database_maximum_connection_lifetime
+            .is_some_and(|lifetime| lifetime <= Duration::zero())
+        {
+            return Err(RVocError::NonPositiveDatabaseMaximumConnectionLifetime);
+        }
 
-        Ok(result)
+        if self
+            .database_statement_timeout
+            .is_some_and(|timeout| timeout <= Duration::zero())
+        {
+            return Err(RVocError::NonPositiveDatabaseStatementTimeout);
+        }
+
+        if self
+            .account_inactivity_expiry
+            .is_some_and(|account_inactivity_expiry| account_inactivity_expiry <= Duration::zero())
+        {
+            return Err(RVocError::NonPositiveAccountInactivityExpiry);
+        }
+
+        if self.wiktionary_dump_insertion_batch_size == 0 {
+            return Err(RVocError::ZeroWiktionaryDumpInsertionBatchSize);
+        } else if self.wiktionary_dump_insertion_batch_size
+            > LARGE_WIKTIONARY_DUMP_INSERTION_BATCH_SIZE
+        {
+            warn!(
+                "The wiktionary dump insertion batch size of {} is very large, this may use a lot of memory",
+                self.wiktionary_dump_insertion_batch_size
+            );
+        }
+
+        self.build_argon2_parameters()?;
+
+        Ok(())
     }
 
     pub fn test_configuration() -> Self {
         Self {
             integration_test_mode: true,
+            dev_mode: false,
             postgres_url: "postgres://rvoc@localhost/rvoc".into(),
+            auto_migrate: false,
+            database_verify_connections: true,
+            database_maximum_connection_lifetime: None,
+            database_statement_timeout: None,
             opentelemetry_url: None,
             shutdown_timeout: Duration::try_seconds(30).unwrap(),
             job_queue_poll_interval: Duration::try_seconds(60).unwrap(),
             maximum_transaction_retry_count: 10u64,
+            transaction_retry_base_delay: Duration::try_milliseconds(10).unwrap(),
+            transaction_retry_max_delay: Duration::try_milliseconds(1000).unwrap(),
             api_listen_address: SocketAddr::from(([0, 0, 0, 0], 8093)),
+            api_http2_enabled: true,
+            api_http1_keep_alive: true,
+            api_http2_keep_alive_interval: None,
+            api_http2_keep_alive_timeout: Duration::try_seconds(20).unwrap(),
+            api_http2_max_concurrent_streams: 200,
+            api_tcp_keep_alive: None,
+            api_trim_trailing_slashes: true,
+            api_allowed_origins: Vec::new(),
+            content_security_policy: "default-src 'none'; frame-ancestors 'none'".into(),
+            strict_transport_security: None,
+            cookie_secure: true,
             minimum_username_length: 3,
             maximum_username_length: 50,
             minimum_password_length: 8,
             maximum_password_length: 100,
+            hibp_check_enabled: false,
+            hibp_api_url: "https://api.pwnedpasswords.com/range/".to_owned(),
+            hibp_timeout: Duration::try_seconds(5).unwrap(),
+            maximum_password_rating_breach_checks_per_minute: 60,
             password_pepper: "abc123abc123".into(),
+            previous_password_peppers: Vec::new(),
             password_argon2id_minimum_memory_kib: 19456,
             password_argon2id_minimum_iterations: 2,
             password_argon2id_parallelism: 1,
             maximum_session_id_generation_retry_count: 10,
+            session_time_to_live: Duration::try_hours(720).unwrap(),
+            session_minimum_renewal_interval: Duration::try_seconds(300).unwrap(),
+            maximum_sessions_per_user: 20,
+            session_delete_batch_size: 1000,
+            maximum_api_token_label_length: 100,
+            maximum_word_note_length: 2000,
+            maximum_word_notes_per_user: 100_000,
+            maximum_word_report_reason_length: 1000,
+            maximum_open_word_reports_per_user: 20,
+            maximum_common_words_limit: 1000,
+            maximum_word_batch_size: 1000,
+            maximum_word_length: 200,
+            default_languages: Vec::new(),
             wiktionary_temporary_data_directory: "wiktionary_data".into(),
             wiktionary_dump_insertion_batch_size: 1000,
             wiktionary_update_interval: Duration::try_hours(24).unwrap(),
+            maximum_wiktionary_update_retry_count: 3,
+            wiktionary_update_retry_delay: Duration::try_minutes(5).unwrap(),
             delete_expired_sessions_interval: Duration::try_hours(24).unwrap(),
+            refresh_word_stats_interval: Duration::try_minutes(60).unwrap(),
+            account_inactivity_expiry: None,
+            expire_inactive_accounts_interval: Duration::try_hours(24).unwrap(),
         }
     }
 
@@ -253,6 +756,84 @@ impl Configuration {
         }
     }
 
+    pub fn verify_api_token_label_length(&self, label: &str) -> RVocResult<()> {
+        if label.len() > self.maximum_api_token_label_length {
+            Err(UserError::ApiTokenLabelLength {
+                actual: label.len(),
+                maximum: self.maximum_api_token_label_length,
+            })?
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn verify_word_note_length(&self, note: &str) -> RVocResult<()> {
+        if note.len() > self.maximum_word_note_length {
+            Err(UserError::WordNoteLength {
+                actual: note.len(),
+                maximum: self.maximum_word_note_length,
+            })?
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn verify_word_report_reason_length(&self, reason: &str) -> RVocResult<()> {
+        if reason.len() > self.maximum_word_report_reason_length {
+            Err(UserError::WordReportReasonLength {
+                actual: reason.len(),
+                maximum: self.maximum_word_report_reason_length,
+            })?
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn verify_common_words_limit(&self, limit: usize) -> RVocResult<()> {
+        if limit > self.maximum_common_words_limit {
+            Err(UserError::CommonWordsLimit {
+                actual: limit,
+                maximum: self.maximum_common_words_limit,
+            })?
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn verify_word_batch_size(&self, batch_size: usize) -> RVocResult<()> {
+        if batch_size > self.maximum_word_batch_size {
+            Err(UserError::WordBatchSize {
+                actual: batch_size,
+                maximum: self.maximum_word_batch_size,
+            })?
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn verify_word_length(&self, word: &str) -> RVocResult<()> {
+        if word.len() > self.maximum_word_length {
+            Err(UserError::WordLength {
+                actual: word.len(),
+                maximum: self.maximum_word_length,
+            })?
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns false if the job should be skipped.
+    /// Disabled jobs are still rescheduled as normal.
+    pub fn should_run_job(&self, job_name: JobName) -> bool {
+        match job_name {
+            // This downloads gigabytes of data.
+            JobName::UpdateWiktionary => !self.integration_test_mode,
+            JobName::DeleteExpiredSessions
+            | JobName::RefreshWordStats
+            | JobName::ExpireInactiveAccounts => true,
+        }
+    }
+
     pub fn verify_password_length(&self, password: &SecureBytes) -> RVocResult<()> {
         let unsecure_password = password.unsecure();
         if unsecure_password.len() < self.minimum_password_length
@@ -319,7 +900,73 @@ where
     }
 }
 
-#[allow(dead_code)]
+/// Use the given password pepper, or in dev mode, fall back to the pepper stored in `dev_password_pepper_file`.
+/// If the file does not exist, it is created with a random pepper,
+/// such that passwords stay valid across restarts.
+/// The file is only readable and writable by its owner.
+fn resolve_password_pepper(
+    password_pepper: Option<SecureBytes>,
+    dev_mode: bool,
+    dev_password_pepper_file: &Path,
+) -> RVocResult<SecureBytes> {
+    use rand::{distributions::Alphanumeric, Rng};
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    match password_pepper {
+        Some(password_pepper) => Ok(password_pepper),
+        None if dev_mode => {
+            if dev_password_pepper_file.exists() {
+                let password_pepper =
+                    std::fs::read_to_string(dev_password_pepper_file).map_err(|error| {
+                        RVocError::ReadDevPasswordPepper {
+                            path: dev_password_pepper_file.to_owned(),
+                            source: Box::new(error),
+                        }
+                    })?;
+                Ok(password_pepper.trim().into())
+            } else {
+                let password_pepper: String = rand::rngs::OsRng
+                    .sample_iter(&Alphanumeric)
+                    .take(32)
+                    .map(char::from)
+                    .collect();
+                std::fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .mode(0o600)
+                    .open(dev_password_pepper_file)
+                    .and_then(|mut file| file.write_all(password_pepper.as_bytes()))
+                    .map_err(|error| RVocError::WriteDevPasswordPepper {
+                        path: dev_password_pepper_file.to_owned(),
+                        source: Box::new(error),
+                    })?;
+                Ok(password_pepper.into())
+            }
+        }
+        None => Err(RVocError::MissingEnvironmentVariable {
+            key: "PASSWORD_PEPPER".to_owned(),
+        }),
+    }
+}
+
+fn read_optional_env_var_as_type<T: FromStr>(key: &str) -> RVocResult<Option<T>>
+where
+    <T as FromStr>::Err: 'static + Error + Send + Sync,
+{
+    read_optional_env_var(key)?
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|error| RVocError::MalformedEnvironmentVariable {
+                    key: key.to_string(),
+                    value: value.into(),
+                    source: Box::new(error),
+                })
+        })
+        .transpose()
+}
+
 fn read_env_var_with_default(key: &str, default: impl Into<String>) -> RVocResult<String> {
     match std::env::var(key) {
         Ok(value) => Ok(value),
@@ -358,3 +1005,117 @@ impl AsRef<Configuration> for &'_ Configuration {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::RVocError, job_queue::JobName};
+
+    use super::{resolve_password_pepper, Configuration};
+
+    #[test]
+    fn test_test_configuration_is_valid() {
+        Configuration::test_configuration().validate().unwrap();
+    }
+
+    #[test]
+    fn test_resolve_password_pepper() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dev_password_pepper_file = std::env::temp_dir().join(format!(
+            "rvoc-test-dev-password-pepper-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&dev_password_pepper_file);
+
+        // production mode requires an explicit pepper
+        assert!(matches!(
+            resolve_password_pepper(None, false, &dev_password_pepper_file),
+            Err(RVocError::MissingEnvironmentVariable { .. })
+        ));
+        assert!(!dev_password_pepper_file.exists());
+
+        // an explicit pepper is always used as is
+        let password_pepper = resolve_password_pepper(
+            Some("explicit-pepper".into()),
+            true,
+            &dev_password_pepper_file,
+        )
+        .unwrap();
+        assert_eq!(password_pepper.unsecure(), b"explicit-pepper");
+        assert!(!dev_password_pepper_file.exists());
+
+        // dev mode generates a valid pepper and reuses it afterwards
+        let mut configuration = Configuration::test_configuration();
+        configuration.password_pepper =
+            resolve_password_pepper(None, true, &dev_password_pepper_file).unwrap();
+        configuration.validate().unwrap();
+        assert_eq!(
+            std::fs::metadata(&dev_password_pepper_file)
+                .unwrap()
+                .permissions()
+                .mode()
+                & 0o777,
+            0o600
+        );
+        assert_eq!(
+            resolve_password_pepper(None, true, &dev_password_pepper_file)
+                .unwrap()
+                .unsecure(),
+            configuration.password_pepper.unsecure()
+        );
+
+        std::fs::remove_file(&dev_password_pepper_file).unwrap();
+    }
+
+    #[test]
+    fn test_should_run_job() {
+        let mut configuration = Configuration::test_configuration();
+        configuration.integration_test_mode = true;
+        assert!(!configuration.should_run_job(JobName::UpdateWiktionary));
+        assert!(configuration.should_run_job(JobName::DeleteExpiredSessions));
+        assert!(configuration.should_run_job(JobName::RefreshWordStats));
+        assert!(configuration.should_run_job(JobName::ExpireInactiveAccounts));
+
+        configuration.integration_test_mode = false;
+        assert!(configuration.should_run_job(JobName::UpdateWiktionary));
+    }
+
+    #[test]
+    fn test_zero_wiktionary_dump_insertion_batch_size() {
+        let mut configuration = Configuration::test_configuration();
+        configuration.wiktionary_dump_insertion_batch_size = 0;
+        assert!(matches!(
+            configuration.validate(),
+            Err(RVocError::ZeroWiktionaryDumpInsertionBatchSize)
+        ));
+
+        configuration.wiktionary_dump_insertion_batch_size = 1;
+        configuration.validate().unwrap();
+    }
+
+    #[test]
+    fn test_zero_session_delete_batch_size() {
+        let mut configuration = Configuration::test_configuration();
+        configuration.session_delete_batch_size = 0;
+        assert!(matches!(
+            configuration.validate(),
+            Err(RVocError::ZeroSessionDeleteBatchSize)
+        ));
+
+        configuration.session_delete_batch_size = 1;
+        configuration.validate().unwrap();
+    }
+
+    #[test]
+    fn test_non_positive_hibp_timeout() {
+        let mut configuration = Configuration::test_configuration();
+        configuration.hibp_timeout = chrono::Duration::zero();
+        assert!(matches!(
+            configuration.validate(),
+            Err(RVocError::NonPositiveHibpTimeout)
+        ));
+
+        configuration.hibp_timeout = chrono::Duration::try_milliseconds(1).unwrap();
+        configuration.validate().unwrap();
+    }
+}
@@ -0,0 +1,54 @@
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use tracing::instrument;
+
+use crate::{
+    configuration::Configuration,
+    error::{RVocError, RVocResult},
+};
+
+diesel::sql_function!(fn pg_try_advisory_lock(key: diesel::sql_types::BigInt) -> diesel::sql_types::Bool);
+
+/// The keys of the advisory locks used by the application.
+/// The values must be unique, and must not be changed while an old version of the application may still be running.
+#[derive(Debug, Clone, Copy)]
+pub enum AdvisoryLockKey {
+    UpdateWiktionary = 1,
+}
+
+/// A postgres session-level advisory lock.
+///
+/// The lock is held by a dedicated connection that is not part of the connection pool.
+/// Dropping this closes the connection, which releases the lock.
+/// This way, the lock is also released if the process crashes.
+pub struct AdvisoryLock {
+    _database_connection: AsyncPgConnection,
+}
+
+impl AdvisoryLock {
+    /// Try to acquire the advisory lock with the given key.
+    /// If the lock is held by someone else, then `None` is returned without waiting.
+    #[instrument(err, skip(configuration))]
+    pub async fn try_acquire(
+        key: AdvisoryLockKey,
+        configuration: &Configuration,
+    ) -> RVocResult<Option<Self>> {
+        let mut database_connection =
+            AsyncPgConnection::establish(configuration.postgres_url.unsecure())
+                .await
+                .map_err(|error| RVocError::DatabaseConnection {
+                    source: Box::new(error),
+                })?;
+
+        let acquired: bool = diesel::select(pg_try_advisory_lock(key as i64))
+            .get_result(&mut database_connection)
+            .await
+            .map_err(|error| RVocError::AcquireAdvisoryLock {
+                key,
+                source: Box::new(error),
+            })?;
+
+        Ok(acquired.then_some(Self {
+            _database_connection: database_connection,
+        }))
+    }
+}
@@ -0,0 +1,87 @@
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use tracing::instrument;
+
+use crate::{
+    configuration::Configuration,
+    error::{RVocError, RVocResult},
+    model::user::username::Username,
+};
+
+use super::RVocAsyncDatabaseConnectionPool;
+
+/// A login via API token is recorded at most once per this many hours,
+/// such that authenticating a request does not write to the users table every time.
+const API_TOKEN_LOGIN_RECORD_INTERVAL_HOURS: i64 = 24;
+
+/// Find the user owning the given API token.
+/// Returns `None` if the token does not exist or is expired,
+/// or if the account of its owner is expired or has no password.
+///
+/// Using a token counts as a login, such that accounts that are only used via API tokens do not get expired.
+/// It is recorded only if the last recorded login is older than [`API_TOKEN_LOGIN_RECORD_INTERVAL_HOURS`].
+#[instrument(err, skip(token, database_connection_pool, configuration))]
+pub async fn find_api_token_user(
+    token: &str,
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+    configuration: &Configuration,
+) -> RVocResult<Option<Username>> {
+    let token_hash = hash_api_token(token);
+    let last_login_cutoff =
+        Utc::now() - chrono::Duration::try_hours(API_TOKEN_LOGIN_RECORD_INTERVAL_HOURS).unwrap();
+
+    let username = database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::{api_tokens, users};
+                    use diesel::dsl::now;
+                    use diesel::BoolExpressionMethods;
+                    use diesel::ExpressionMethods;
+                    use diesel::OptionalExtension;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let username = api_tokens::table
+                        .inner_join(users::table)
+                        .select(api_tokens::username)
+                        .filter(api_tokens::token_hash.eq(&token_hash))
+                        .filter(api_tokens::expiry.is_null().or(api_tokens::expiry.gt(now)))
+                        .filter(users::password_hash.is_not_null())
+                        .filter(users::expired_at.is_null())
+                        .first::<String>(database_connection)
+                        .await
+                        .optional()?;
+
+                    if let Some(username) = &username {
+                        diesel::update(users::table)
+                            .filter(users::name.eq(username))
+                            .filter(users::last_login_at.lt(last_login_cutoff))
+                            .set(users::last_login_at.eq(now))
+                            .execute(database_connection)
+                            .await?;
+                    }
+
+                    Ok(username)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| RVocError::AuthenticateApiToken {
+            source: Box::new(error),
+        })?;
+
+    username
+        .map(|username| Username::new(username, configuration))
+        .transpose()
+}
+
+/// API tokens are long random strings, so a fast unsalted hash is sufficient to protect them at rest.
+pub fn hash_api_token(token: &str) -> String {
+    to_hex(&Sha256::digest(token.as_bytes()))
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -1,24 +1,102 @@
-use diesel_async::{pooled_connection::deadpool::Pool, AsyncPgConnection};
+use diesel::ConnectionError;
+use diesel_async::{
+    pooled_connection::{
+        deadpool::{Hook, HookError, Pool},
+        AsyncDieselConnectionManager, ManagerConfig, RecyclingMethod,
+    },
+    AsyncConnection, AsyncPgConnection, RunQueryDsl,
+};
 use tracing::instrument;
 
 use crate::{configuration::Configuration, error::RVocResult};
 
+use super::transactions::TransactionRetryBackoff;
+
 #[derive(Clone)]
 pub struct RVocAsyncDatabaseConnectionPool {
     pub(super) implementation: Pool<AsyncPgConnection>,
+    pub(super) retry_backoff: TransactionRetryBackoff,
 }
 
 impl RVocAsyncDatabaseConnectionPool {
     #[instrument(err, skip(configuration))]
     pub(super) fn new(configuration: &Configuration) -> RVocResult<Self> {
-        // create a new connection pool with the default config
-        let connection_manager = diesel_async::pooled_connection::AsyncDieselConnectionManager::<
-            diesel_async::AsyncPgConnection,
-        >::new(configuration.postgres_url.unsecure());
-        let pool = Pool::builder(connection_manager).build()?;
+        let mut manager_config = ManagerConfig::default();
+        manager_config.recycling_method = if configuration.database_verify_connections {
+            RecyclingMethod::Verified
+        } else {
+            RecyclingMethod::Fast
+        };
+
+        if let Some(statement_timeout) = configuration.database_statement_timeout {
+            let statement_timeout = statement_timeout.num_milliseconds();
+            manager_config.custom_setup = Box::new(move |url| {
+                Box::pin(async move {
+                    let mut connection = AsyncPgConnection::establish(url).await?;
+                    // This is a session setting, so it applies to all transactions on this connection.
+                    diesel::sql_query(format!("SET statement_timeout = {statement_timeout}"))
+                        .execute(&mut connection)
+                        .await
+                        .map_err(ConnectionError::CouldntSetupConfiguration)?;
+                    Ok(connection)
+                })
+            });
+        }
+
+        let connection_manager = AsyncDieselConnectionManager::<AsyncPgConnection>::new_with_config(
+            configuration.postgres_url.unsecure(),
+            manager_config,
+        );
+        let mut pool_builder = Pool::builder(connection_manager);
+
+        if let Some(maximum_connection_lifetime) =
+            configuration.database_maximum_connection_lifetime
+        {
+            let maximum_connection_lifetime = maximum_connection_lifetime.to_std().unwrap();
+            // Returning an error from the hook makes the pool drop the connection and use a different one.
+            pool_builder = pool_builder.pre_recycle(Hook::sync_fn(move |_, metrics| {
+                if metrics.age() > maximum_connection_lifetime {
+                    Err(HookError::Continue(None))
+                } else {
+                    Ok(())
+                }
+            }));
+        }
+
+        let pool = pool_builder.build()?;
 
         Ok(Self {
             implementation: pool,
+            retry_backoff: TransactionRetryBackoff::new(configuration),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::configuration::Configuration;
+
+    use super::RVocAsyncDatabaseConnectionPool;
+
+    #[test]
+    fn test_create_pool_with_statement_timeout() {
+        let mut configuration = Configuration::test_configuration();
+        configuration.database_statement_timeout = Some(Duration::try_seconds(5).unwrap());
+
+        // Connections are opened lazily, so this does not require a database.
+        RVocAsyncDatabaseConnectionPool::new(&configuration).unwrap();
+    }
+
+    #[test]
+    fn test_create_pool_with_recycling() {
+        let mut configuration = Configuration::test_configuration();
+        configuration.database_verify_connections = true;
+        configuration.database_maximum_connection_lifetime =
+            Some(Duration::try_minutes(30).unwrap());
+
+        // Connections are opened lazily, so this does not require a database.
+        RVocAsyncDatabaseConnectionPool::new(&configuration).unwrap();
+    }
+}
@@ -0,0 +1,121 @@
+use std::path::Path;
+
+use tracing::{debug, info, instrument};
+
+use crate::{
+    configuration::Configuration,
+    error::{RVocError, RVocResult, UserError},
+};
+
+use super::RVocAsyncDatabaseConnectionPool;
+
+/// The number of lines of a frequency list that are imported in a single transaction.
+const FREQUENCY_RANK_BATCH_SIZE: usize = 1000;
+
+/// Import a frequency list in CSV format with lines of the form `word,rank` into the given language.
+/// Words that do not exist in the database are ignored.
+///
+/// The ranks are updated in batches of [`FREQUENCY_RANK_BATCH_SIZE`] lines with one statement per batch,
+/// and each batch is committed separately, so a large list does not cause a single huge transaction.
+///
+/// Returns the number of updated words.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn import_frequency_list(
+    language: &str,
+    file: &Path,
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+    configuration: &Configuration,
+) -> RVocResult<usize> {
+    let frequency_list =
+        tokio::fs::read_to_string(file)
+            .await
+            .map_err(|error| RVocError::ReadFrequencyList {
+                path: file.to_owned(),
+                source: Box::new(error),
+            })?;
+    let frequency_ranks = frequency_list
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_index, line)| {
+            line.rsplit_once(',')
+                .and_then(|(word, rank)| Some((word.trim(), rank.trim().parse::<i32>().ok()?)))
+                .ok_or_else(|| RVocError::MalformedFrequencyList {
+                    line_number: line_index + 1,
+                    line: line.to_owned(),
+                })
+        })
+        .collect::<RVocResult<Vec<_>>>()?;
+
+    let language_id = database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::languages;
+                    use diesel::ExpressionMethods;
+                    use diesel::OptionalExtension;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    Ok(languages::table
+                        .select(languages::id)
+                        .filter(languages::english_name.eq(language))
+                        .first::<i32>(database_connection)
+                        .await
+                        .optional()?
+                        .ok_or_else(|| UserError::LanguageDoesNotExist {
+                            language: language.to_owned(),
+                        })?)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::ImportFrequencyList {
+                source: Box::new(error),
+            },
+        })?;
+
+    let mut updated_words = 0;
+    for batch in frequency_ranks.chunks(FREQUENCY_RANK_BATCH_SIZE) {
+        let (batch_words, batch_ranks): (Vec<&str>, Vec<i32>) = batch.iter().copied().unzip();
+
+        let updated_batch_words = database_connection_pool
+            .execute_transaction::<_, RVocError>(
+                |database_connection| {
+                    Box::pin(async {
+                        use diesel::sql_types::{Array, Integer, Text};
+                        use diesel_async::RunQueryDsl;
+
+                        Ok(diesel::sql_query(
+                            "UPDATE words SET frequency_rank = frequency_ranks.rank \
+                            FROM unnest($1, $2) AS frequency_ranks(word, rank) \
+                            WHERE words.language = $3 AND words.word = frequency_ranks.word",
+                        )
+                        .bind::<Array<Text>, _>(&batch_words)
+                        .bind::<Array<Integer>, _>(&batch_ranks)
+                        .bind::<Integer, _>(language_id)
+                        .execute(database_connection)
+                        .await?)
+                    })
+                },
+                configuration.maximum_transaction_retry_count,
+            )
+            .await
+            .map_err(|error| RVocError::ImportFrequencyList {
+                source: Box::new(error),
+            })?;
+
+        updated_words += updated_batch_words;
+        debug!("Set the frequency rank of a batch of {updated_batch_words} words");
+    }
+
+    info!(
+        "Set the frequency rank of {updated_words} words from {} lines",
+        frequency_ranks.len()
+    );
+
+    Ok(updated_words)
+}
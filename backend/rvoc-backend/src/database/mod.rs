@@ -8,13 +8,21 @@ use self::migrations::has_missing_migrations;
 pub use self::async_connection_pool::RVocAsyncDatabaseConnectionPool;
 pub use self::sync_connection::RVocSyncDatabaseConnection;
 
+pub mod advisory_lock;
+pub mod api_tokens;
 mod async_connection_pool;
+pub mod frequency_ranks;
 pub mod migrations;
 pub mod model;
+pub mod password_pepper_canary;
 #[rustfmt::skip]
 pub mod schema;
+pub mod seed;
 mod sync_connection;
 pub mod transactions;
+pub mod user_sessions;
+pub mod views;
+pub mod word_reports;
 
 /// Create an async connection pool to the database.
 /// Note that this does not actually open any connections to the database, the connections are opened lazily.
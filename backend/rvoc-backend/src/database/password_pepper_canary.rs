@@ -0,0 +1,118 @@
+use secure_string::SecureBytes;
+use tracing::{info, instrument, warn};
+
+use crate::{
+    configuration::Configuration,
+    error::{RVocError, RVocResult},
+    model::user::password_hash::PasswordHash,
+};
+
+use super::RVocAsyncDatabaseConnectionPool;
+
+/// The name of the setting that stores the canary hash.
+const PASSWORD_PEPPER_CANARY_SETTING: &str = "password_pepper_canary";
+
+/// The password that is hashed to create the canary.
+/// It does not need to be secret, since the canary only detects pepper changes.
+const PASSWORD_PEPPER_CANARY_PASSWORD: &str = "password pepper canary";
+
+/// Check if the configured password pepper can still verify the canary hash stored in the database.
+///
+/// If the pepper is changed without adding the old one to the previous peppers,
+/// all existing password hashes stop verifying, and every login fails with a wrong password.
+/// This check turns that into a warning at startup.
+///
+/// If there is no canary yet, it is created.
+/// If the canary verifies with a previous pepper or with outdated parameters, it is rehashed like a user password.
+///
+/// Returns `false` if the canary does not verify.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn check_password_pepper_canary(
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+    configuration: &Configuration,
+) -> RVocResult<bool> {
+    let canary_password = SecureBytes::from(PASSWORD_PEPPER_CANARY_PASSWORD);
+
+    let canary_hash: Option<String> = database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::settings;
+                    use diesel::ExpressionMethods;
+                    use diesel::OptionalExtension;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    Ok(settings::table
+                        .select(settings::value)
+                        .filter(settings::name.eq(PASSWORD_PEPPER_CANARY_SETTING))
+                        .first(database_connection)
+                        .await
+                        .optional()?)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| RVocError::CheckPasswordPepperCanary {
+            source: Box::new(error),
+        })?;
+
+    let (matches, new_canary_hash) = if let Some(canary_hash) = canary_hash {
+        let mut canary_hash = PasswordHash::from(canary_hash);
+        let verify_result = canary_hash.verify(canary_password, configuration)?;
+        (
+            verify_result.matches,
+            verify_result.modified.then_some(canary_hash),
+        )
+    } else {
+        info!("Creating password pepper canary");
+        (
+            true,
+            Some(PasswordHash::new(canary_password, configuration)?),
+        )
+    };
+
+    if let Some(new_canary_hash) = new_canary_hash {
+        let new_canary_hash = Option::<String>::from(new_canary_hash).unwrap();
+
+        database_connection_pool
+            .execute_transaction::<_, RVocError>(
+                |database_connection| {
+                    Box::pin(async {
+                        use crate::database::schema::settings;
+                        use diesel::ExpressionMethods;
+                        use diesel_async::RunQueryDsl;
+
+                        diesel::insert_into(settings::table)
+                            .values((
+                                settings::name.eq(PASSWORD_PEPPER_CANARY_SETTING),
+                                settings::value.eq(&new_canary_hash),
+                            ))
+                            .on_conflict(settings::name)
+                            .do_update()
+                            .set(settings::value.eq(&new_canary_hash))
+                            .execute(database_connection)
+                            .await?;
+
+                        Ok(())
+                    })
+                },
+                configuration.maximum_transaction_retry_count,
+            )
+            .await
+            .map_err(|error| RVocError::CheckPasswordPepperCanary {
+                source: Box::new(error),
+            })?;
+    }
+
+    if !matches {
+        warn!(
+            "The password pepper canary does not verify with the configured password pepper. \
+             The pepper was likely changed without adding the old pepper to PREVIOUS_PASSWORD_PEPPERS, \
+             so existing users will not be able to log in."
+        );
+    }
+
+    Ok(matches)
+}
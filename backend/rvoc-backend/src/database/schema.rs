@@ -1,5 +1,49 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    /// Representation of the `api_tokens` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    api_tokens (id) {
+        /// The `id` column of the `api_tokens` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `username` column of the `api_tokens` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        username -> Text,
+        /// The `token_hash` column of the `api_tokens` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        token_hash -> Text,
+        /// The `label` column of the `api_tokens` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        label -> Text,
+        /// The `created_at` column of the `api_tokens` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamptz,
+        /// The `expiry` column of the `api_tokens` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamptz>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        expiry -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     /// Representation of the `job_queue` table.
     ///
@@ -70,6 +114,32 @@ diesel::table! {
         /// (Automatically generated by Diesel.)
         #[max_length = 50]
         username -> Nullable<Varchar>,
+        /// The `created_at` column of the `sessions` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    /// Representation of the `settings` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    settings (name) {
+        /// The `name` column of the `settings` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        name -> Text,
+        /// The `value` column of the `settings` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        value -> Text,
     }
 }
 
@@ -93,6 +163,88 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    /// Representation of the `translations` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    translations (language, word, word_type, translation_language, translation_word, translation_word_type) {
+        /// The `word` column of the `translations` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        word -> Text,
+        /// The `word_type` column of the `translations` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        word_type -> Int4,
+        /// The `language` column of the `translations` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        language -> Int4,
+        /// The `translation_word` column of the `translations` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        translation_word -> Text,
+        /// The `translation_word_type` column of the `translations` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        translation_word_type -> Int4,
+        /// The `translation_language` column of the `translations` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        translation_language -> Int4,
+    }
+}
+
+diesel::table! {
+    /// Representation of the `user_word_notes` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    user_word_notes (username, word, word_type, language) {
+        /// The `username` column of the `user_word_notes` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        username -> Text,
+        /// The `word` column of the `user_word_notes` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        word -> Text,
+        /// The `word_type` column of the `user_word_notes` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        word_type -> Int4,
+        /// The `language` column of the `user_word_notes` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        language -> Int4,
+        /// The `note` column of the `user_word_notes` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        note -> Text,
+    }
+}
+
 diesel::table! {
     /// Representation of the `users` table.
     ///
@@ -110,6 +262,100 @@ diesel::table! {
         ///
         /// (Automatically generated by Diesel.)
         password_hash -> Nullable<Text>,
+        /// The `last_login_at` column of the `users` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        last_login_at -> Timestamptz,
+        /// The `expired_at` column of the `users` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamptz>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        expired_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    /// Representation of the `word_forms` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    word_forms (language, form, lemma) {
+        /// The `language` column of the `word_forms` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        language -> Int4,
+        /// The `form` column of the `word_forms` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        form -> Text,
+        /// The `lemma` column of the `word_forms` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        lemma -> Text,
+    }
+}
+
+diesel::table! {
+    /// Representation of the `word_reports` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    word_reports (id) {
+        /// The `id` column of the `word_reports` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `username` column of the `word_reports` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        username -> Text,
+        /// The `language` column of the `word_reports` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        language -> Text,
+        /// The `word` column of the `word_reports` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        word -> Text,
+        /// The `word_type` column of the `word_reports` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        word_type -> Text,
+        /// The `reason` column of the `word_reports` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        reason -> Text,
+        /// The `created_at` column of the `word_reports` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamptz,
+        /// The `resolved_at` column of the `word_reports` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamptz>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        resolved_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -156,19 +402,35 @@ diesel::table! {
         ///
         /// (Automatically generated by Diesel.)
         language -> Int4,
+        /// The `frequency_rank` column of the `words` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        frequency_rank -> Nullable<Int4>,
     }
 }
 
+diesel::joinable!(api_tokens -> users (username));
 diesel::joinable!(sessions -> users (username));
+diesel::joinable!(user_word_notes -> users (username));
+diesel::joinable!(word_forms -> languages (language));
+diesel::joinable!(word_reports -> users (username));
 diesel::joinable!(words -> languages (language));
 diesel::joinable!(words -> word_types (word_type));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_tokens,
     job_queue,
     languages,
     sessions,
+    settings,
     test_can_be_safely_dropped_in_production,
+    translations,
+    user_word_notes,
     users,
+    word_forms,
+    word_reports,
     word_types,
     words,
 );
@@ -0,0 +1,52 @@
+use tracing::{info, instrument};
+
+use crate::{
+    configuration::Configuration,
+    error::{RVocError, RVocResult},
+};
+
+use super::RVocAsyncDatabaseConnectionPool;
+
+/// Insert the configured default languages if they do not exist yet.
+/// This is idempotent, so it is safe to run on every startup.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn seed_default_languages(
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+    configuration: &Configuration,
+) -> RVocResult<()> {
+    if configuration.default_languages.is_empty() {
+        return Ok(());
+    }
+
+    let inserted_languages = database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::languages;
+                    use diesel::ExpressionMethods;
+                    use diesel_async::RunQueryDsl;
+
+                    Ok(diesel::insert_into(languages::table)
+                        .values(
+                            configuration
+                                .default_languages
+                                .iter()
+                                .map(|language| languages::english_name.eq(language))
+                                .collect::<Vec<_>>(),
+                        )
+                        .on_conflict_do_nothing()
+                        .execute(database_connection)
+                        .await?)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| RVocError::SeedDefaultLanguages {
+            source: Box::new(error),
+        })?;
+
+    info!("Seeded {inserted_languages} missing default languages");
+
+    Ok(())
+}
@@ -1,16 +1,21 @@
-use std::error::Error;
+use std::{error::Error, time::Duration};
 
 use diesel::PgConnection;
 use diesel_async::AsyncPgConnection;
+use rand::Rng;
 use tracing::{debug, instrument};
 
-use crate::error::{BoxDynError, RVocError, UserError};
+use crate::{
+    configuration::Configuration,
+    error::{BoxDynError, RVocError, UserError},
+};
 
 use super::{RVocAsyncDatabaseConnectionPool, RVocSyncDatabaseConnection};
 
 impl RVocAsyncDatabaseConnectionPool {
     /// Execute an asynchronous database transaction and retry on failure.
-    /// Temporary failures are logged and the transaction is retried (by calling the closure again).
+    /// Temporary failures are logged and the transaction is retried (by calling the closure again)
+    /// after an exponentially increasing delay.
     /// Permanent failures cause the function to return immediately.
     ///
     /// If `max_retries` temporary errors have occurred, then [`PermanentError::too_many_temporary_errors`] is returned.
@@ -85,13 +90,19 @@ impl RVocAsyncDatabaseConnectionPool {
         max_retries: u64,
         isolation_level: TransactionIsolationLevel,
     ) -> Result<ReturnType, PermanentErrorType> {
-        let mut database_connection = self.implementation.get().await.map_err(|error| {
-            PermanentErrorType::permanent_error(Box::new(RVocError::DatabaseConnection {
-                source: Box::new(error),
-            }))
-        })?;
+        for retry in 0..max_retries.saturating_add(1) {
+            if retry > 0 {
+                tokio::time::sleep(self.retry_backoff.delay(retry)).await;
+            }
+
+            // The connection is acquired for each attempt and returned to the pool before the backoff,
+            // such that waiting transactions do not exhaust the pool.
+            let mut database_connection = self.implementation.get().await.map_err(|error| {
+                PermanentErrorType::permanent_error(Box::new(RVocError::DatabaseConnection {
+                    source: Box::new(error),
+                }))
+            })?;
 
-        for _ in 0..max_retries.saturating_add(1) {
             let transaction_result = match isolation_level {
                 TransactionIsolationLevel::Serializable => {
                     database_connection.build_transaction().serializable()
@@ -125,50 +136,6 @@ impl RVocAsyncDatabaseConnectionPool {
 
         Err(PermanentErrorType::too_many_temporary_errors(max_retries))
     }
-
-    /// Execute an asynchronous database transactio without retries.
-    #[instrument(err, skip(self, transaction))]
-    #[deprecated(
-        note = "postgres transactions may fail randomly because of some optimisations in postgres"
-    )]
-    #[allow(dead_code)]
-    pub async fn execute_transaction_without_retries<
-        'b,
-        ReturnType: 'b + Send,
-        ErrorType: 'b + Send + PermanentTransactionError,
-    >(
-        &self,
-        transaction: impl for<'r> FnOnce(
-                &'r mut AsyncPgConnection,
-            ) -> diesel_async::scoped_futures::ScopedBoxFuture<
-                'b,
-                'r,
-                Result<ReturnType, ErrorType>,
-            > + Send
-            + Sync,
-    ) -> Result<ReturnType, ErrorType> {
-        let mut database_connection = self.implementation.get().await.map_err(|error| {
-            ErrorType::permanent_error(Box::new(RVocError::DatabaseConnection {
-                source: Box::new(error),
-            }))
-        })?;
-
-        database_connection
-            .build_transaction()
-            .serializable()
-            .run(|database_connection| {
-                Box::pin(async move {
-                    transaction(database_connection)
-                        .await
-                        .map_err(|error| FromDieselError::ErrorType(error))
-                })
-            })
-            .await
-            .map_err(|error| match error {
-                FromDieselError::ErrorType(error) => error,
-                FromDieselError::Diesel(error) => ErrorType::permanent_error(Box::new(error)),
-            })
-    }
 }
 
 impl RVocSyncDatabaseConnection {
@@ -271,14 +238,31 @@ impl TooManyTemporaryTransactionErrors for RVocError {
     }
 }
 
-enum FromDieselError<ErrorType> {
-    ErrorType(ErrorType),
-    Diesel(diesel::result::Error),
+/// The delays between retries of a failed asynchronous transaction.
+#[derive(Clone, Copy, Debug)]
+pub struct TransactionRetryBackoff {
+    base_delay: Duration,
+    max_delay: Duration,
 }
 
-impl<ErrorType> From<diesel::result::Error> for FromDieselError<ErrorType> {
-    fn from(value: diesel::result::Error) -> Self {
-        Self::Diesel(value)
+impl TransactionRetryBackoff {
+    pub fn new(configuration: &Configuration) -> Self {
+        Self {
+            base_delay: configuration.transaction_retry_base_delay.to_std().unwrap(),
+            max_delay: configuration.transaction_retry_max_delay.to_std().unwrap(),
+        }
+    }
+
+    /// The delay before the given retry, where the first retry is `1`.
+    /// It doubles with each retry up to the maximum, and up to half of it is subtracted randomly.
+    fn delay(&self, retry: u64) -> Duration {
+        let exponent = u32::try_from(retry.saturating_sub(1)).unwrap_or(u32::MAX);
+        let delay = 2u32
+            .checked_pow(exponent)
+            .and_then(|factor| self.base_delay.checked_mul(factor))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay));
+
+        delay - delay.mul_f64(rand::thread_rng().gen_range(0.0..=0.5))
     }
 }
 
@@ -287,3 +271,30 @@ enum TransactionIsolationLevel {
     Serializable,
     ReadCommitted,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::TransactionRetryBackoff;
+
+    #[test]
+    fn test_retry_backoff_delay() {
+        let backoff = TransactionRetryBackoff {
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(100),
+        };
+
+        for (retry, maximum_delay) in [(1, 10), (2, 20), (3, 40), (4, 80), (5, 100), (1000, 100)] {
+            let maximum_delay = Duration::from_millis(maximum_delay);
+            for _ in 0..100 {
+                let delay = backoff.delay(retry);
+                assert!(
+                    maximum_delay / 2 <= delay && delay <= maximum_delay,
+                    "retry {retry}: {delay:?} not in [{:?}, {maximum_delay:?}]",
+                    maximum_delay / 2
+                );
+            }
+        }
+    }
+}
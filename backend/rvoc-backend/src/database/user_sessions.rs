@@ -0,0 +1,63 @@
+use tracing::{debug, instrument};
+
+use crate::{
+    configuration::Configuration,
+    error::{RVocError, RVocResult},
+};
+
+use super::RVocAsyncDatabaseConnectionPool;
+
+/// Delete all sessions of the given user in batches of at most [`Configuration::session_delete_batch_size`] sessions.
+/// Each batch is committed separately, so a user with many sessions does not cause a single huge transaction.
+///
+/// Sessions that are created concurrently may survive this,
+/// so they need to be deleted again in the same transaction that deletes the user.
+///
+/// Returns the number of deleted sessions.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn delete_sessions_of_user_in_batches(
+    username: &str,
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+    configuration: &Configuration,
+) -> RVocResult<usize> {
+    let batch_size = configuration.session_delete_batch_size;
+    let mut deleted_session_count = 0;
+
+    loop {
+        let deleted_batch_size = database_connection_pool
+            .execute_transaction::<_, RVocError>(
+                |database_connection| {
+                    Box::pin(async move {
+                        use crate::database::schema::sessions;
+                        use diesel::ExpressionMethods;
+                        use diesel::QueryDsl;
+                        use diesel_async::RunQueryDsl;
+
+                        Ok(diesel::delete(sessions::table)
+                            .filter(
+                                sessions::id.eq_any(
+                                    sessions::table
+                                        .select(sessions::id)
+                                        .filter(sessions::username.eq(username))
+                                        .limit(batch_size.try_into().unwrap_or(i64::MAX)),
+                                ),
+                            )
+                            .execute(database_connection)
+                            .await?)
+                    })
+                },
+                configuration.maximum_transaction_retry_count,
+            )
+            .await
+            .map_err(|error| RVocError::DeleteUserSessions {
+                source: Box::new(error),
+            })?;
+
+        deleted_session_count += deleted_batch_size;
+        debug!("Deleted a batch of {deleted_batch_size} sessions");
+
+        if deleted_batch_size < batch_size {
+            return Ok(deleted_session_count);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Diesel does not generate schema definitions for views, so they are declared manually here.
+
+use crate::database::schema::languages;
+
+diesel::table! {
+    /// The materialized view `language_word_counts`, containing the number of words per language.
+    /// It is refreshed by [`JobName::RefreshWordStats`](crate::job_queue::JobName::RefreshWordStats).
+    language_word_counts (language) {
+        language -> Int4,
+        word_count -> Int8,
+    }
+}
+
+diesel::joinable!(language_word_counts -> languages (language));
+diesel::allow_tables_to_appear_in_same_query!(language_word_counts, languages);
@@ -0,0 +1,173 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use diesel::{Queryable, Selectable};
+use tracing::{info, instrument};
+
+use crate::{
+    configuration::Configuration,
+    error::{RVocError, RVocResult, UserError},
+};
+
+use super::RVocAsyncDatabaseConnectionPool;
+
+/// The format of the times when displaying a [`WordReport`].
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S UTC";
+
+/// A problem with the dictionary data reported by a user.
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::database::schema::word_reports)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WordReport {
+    pub id: i32,
+    pub username: String,
+    pub language: String,
+    pub word: String,
+    pub word_type: String,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// Formats the report as a single line, for listing reports in the CLI.
+/// The reason is quoted, such that line breaks in it do not break the listing.
+impl Display for WordReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            id,
+            username,
+            language,
+            word,
+            word_type,
+            reason,
+            created_at,
+            resolved_at,
+        } = self;
+        let created_at = created_at.format(DATE_TIME_FORMAT);
+        write!(
+            f,
+            "#{id} {created_at} by {username}: {language} {word:?} ({word_type}): {reason:?}"
+        )?;
+
+        if let Some(resolved_at) = resolved_at {
+            write!(f, " (resolved {})", resolved_at.format(DATE_TIME_FORMAT))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// List the word reports, oldest first.
+/// Resolved reports are only listed if `include_resolved` is set.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn list_word_reports(
+    include_resolved: bool,
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+    configuration: &Configuration,
+) -> RVocResult<Vec<WordReport>> {
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::word_reports;
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel::SelectableHelper;
+                    use diesel_async::RunQueryDsl;
+
+                    let mut query = word_reports::table
+                        .select(WordReport::as_select())
+                        .order_by(word_reports::id)
+                        .into_boxed();
+                    if !include_resolved {
+                        query = query.filter(word_reports::resolved_at.is_null());
+                    }
+
+                    Ok(query.load(database_connection).await?)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| RVocError::ListWordReports {
+            source: Box::new(error),
+        })
+}
+
+/// Mark an unresolved word report as resolved.
+/// If there is no unresolved report with the given id, then [`UserError::WordReportDoesNotExist`] is returned.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn resolve_word_report(
+    id: i32,
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+    configuration: &Configuration,
+) -> RVocResult<()> {
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::word_reports;
+                    use diesel::dsl::now;
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let affected_rows = diesel::update(word_reports::table)
+                        .filter(word_reports::id.eq(id))
+                        .filter(word_reports::resolved_at.is_null())
+                        .set(word_reports::resolved_at.eq(now))
+                        .execute(database_connection)
+                        .await?;
+
+                    if affected_rows == 0 {
+                        Err(UserError::WordReportDoesNotExist { id }.into())
+                    } else {
+                        Ok(())
+                    }
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::ResolveWordReport {
+                source: Box::new(error),
+            },
+        })?;
+
+    info!("Resolved word report {id}");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::WordReport;
+
+    #[test]
+    fn test_display_word_report() {
+        let mut word_report = WordReport {
+            id: 12,
+            username: "alice".to_owned(),
+            language: "English".to_owned(),
+            word: "run".to_owned(),
+            word_type: "Verb".to_owned(),
+            reason: "also a noun\nand an adjective".to_owned(),
+            created_at: Utc.with_ymd_and_hms(2024, 3, 23, 10, 5, 0).unwrap(),
+            resolved_at: None,
+        };
+        assert_eq!(
+            word_report.to_string(),
+            r#"#12 2024-03-23 10:05:00 UTC by alice: English "run" (Verb): "also a noun\nand an adjective""#
+        );
+
+        word_report.resolved_at = Some(Utc.with_ymd_and_hms(2024, 3, 24, 8, 0, 0).unwrap());
+        assert_eq!(
+            word_report.to_string(),
+            r#"#12 2024-03-23 10:05:00 UTC by alice: English "run" (Verb): "also a noun\nand an adjective" (resolved 2024-03-24 08:00:00 UTC)"#
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use std::{error::Error, ffi::OsString, path::PathBuf};
 
+use api_commands::WordIdentifier;
 use thiserror::Error;
 
+use crate::database::advisory_lock::AdvisoryLockKey;
+
 pub type RVocResult<T> = Result<T, RVocError>;
 pub type BoxDynError = Box<dyn Error + Send + Sync>;
 
@@ -24,6 +27,48 @@ pub enum RVocError {
     #[error("the configured job queue poll interval is negative")]
     NegativeJobQueuePollInterval,
 
+    #[error("the configured wiktionary update retry delay is negative")]
+    NegativeWiktionaryUpdateRetryDelay,
+
+    #[error("the session renewal interval must satisfy 0 <= minimum renewal interval ({minimum_renewal_interval}) < time to live ({time_to_live})")]
+    SessionRenewalIntervalBounds {
+        minimum_renewal_interval: chrono::Duration,
+        time_to_live: chrono::Duration,
+    },
+
+    #[error("the transaction retry delays must satisfy 0 <= base delay ({base_delay}) <= max delay ({max_delay})")]
+    TransactionRetryDelayBounds {
+        base_delay: chrono::Duration,
+        max_delay: chrono::Duration,
+    },
+
+    #[error("the configured keep-alive duration {name} is not positive")]
+    NonPositiveApiKeepAliveDuration { name: &'static str },
+
+    #[error("the configured maximum number of concurrent HTTP/2 streams is zero")]
+    ZeroApiHttp2MaxConcurrentStreams,
+
+    #[error("the configured maximum number of sessions per user is zero")]
+    ZeroMaximumSessionsPerUser,
+
+    #[error("the configured maximum database connection lifetime is not positive")]
+    NonPositiveDatabaseMaximumConnectionLifetime,
+
+    #[error("the configured database statement timeout is not positive")]
+    NonPositiveDatabaseStatementTimeout,
+
+    #[error("the configured account inactivity expiry is not positive")]
+    NonPositiveAccountInactivityExpiry,
+
+    #[error("the configured wiktionary dump insertion batch size is zero")]
+    ZeroWiktionaryDumpInsertionBatchSize,
+
+    #[error("the configured session delete batch size is zero")]
+    ZeroSessionDeleteBatchSize,
+
+    #[error("the configured Have I Been Pwned API timeout is not positive")]
+    NonPositiveHibpTimeout,
+
     #[error("setting up tracing failed: {source}")]
     SetupTracing { source: BoxDynError },
 
@@ -44,9 +89,24 @@ pub enum RVocError {
     )]
     DatabaseTransactionRetryLimitReached { limit: u64 },
 
+    #[error("error acquiring the advisory lock {key:?}: {source}")]
+    AcquireAdvisoryLock {
+        key: AdvisoryLockKey,
+        source: BoxDynError,
+    },
+
+    #[error("error checking the password pepper canary: {source}")]
+    CheckPasswordPepperCanary { source: BoxDynError },
+
     #[error("error executing the database migrations: {source}")]
     DatabaseMigration { source: BoxDynError },
 
+    #[error("the value of the security header {header} is malformed: {source}")]
+    MalformedSecurityHeader {
+        header: &'static str,
+        source: BoxDynError,
+    },
+
     #[error("error while serving API request: {source}")]
     ApiServerError { source: BoxDynError },
 
@@ -75,36 +135,123 @@ pub enum RVocError {
     #[error("password hashing went wrong: {source}")]
     PasswordArgon2IdHash { source: BoxDynError },
 
+    #[error("the password hashing task failed: {source}")]
+    PasswordHashingTask { source: BoxDynError },
+
     #[error("password verification went wrong: {source}")]
     PasswordArgon2IdVerify { source: BoxDynError },
 
     #[error("password rehashing went wrong: {source}")]
     PasswordArgon2IdRehash { source: BoxDynError },
 
+    #[error("error building the Have I Been Pwned API client: {source}")]
+    BuildHibpClient { source: BoxDynError },
+
     #[error("error creating user: {source}")]
     CreateUser { source: BoxDynError },
 
     #[error("error deleting user: {source}")]
     DeleteUser { source: BoxDynError },
 
+    #[error("error deleting the sessions of a user: {source}")]
+    DeleteUserSessions { source: BoxDynError },
+
+    #[error("error getting information about the logged in user: {source}")]
+    WhoAmI { source: BoxDynError },
+
+    #[error("error getting the security overview of the logged in user: {source}")]
+    AccountSecurity { source: BoxDynError },
+
+    #[error("error creating api token: {source}")]
+    CreateApiToken { source: BoxDynError },
+
+    #[error("error listing api tokens: {source}")]
+    ListApiTokens { source: BoxDynError },
+
+    #[error("error deleting api token: {source}")]
+    DeleteApiToken { source: BoxDynError },
+
+    #[error("error authenticating with api token: {source}")]
+    AuthenticateApiToken { source: BoxDynError },
+
+    #[error("error checking if words exist: {source}")]
+    CheckWordsExist { source: BoxDynError },
+
+    #[error("error detecting the language of a word: {source}")]
+    DetectLanguage { source: BoxDynError },
+
+    #[error("error selecting a random word: {source}")]
+    RandomWord { source: BoxDynError },
+
+    #[error("error listing translations: {source}")]
+    ListTranslations { source: BoxDynError },
+
+    #[error("error finding lemmas: {source}")]
+    FindLemmas { source: BoxDynError },
+
+    #[error("error listing common words: {source}")]
+    ListCommonWords { source: BoxDynError },
+
+    #[error("error reading frequency list {path:?}: {source}")]
+    ReadFrequencyList { path: PathBuf, source: BoxDynError },
+
+    #[error("malformed line {line_number} in frequency list, expected 'word,rank': {line:?}")]
+    MalformedFrequencyList { line_number: usize, line: String },
+
+    #[error("error importing frequency list: {source}")]
+    ImportFrequencyList { source: BoxDynError },
+
+    #[error("error seeding the default languages: {source}")]
+    SeedDefaultLanguages { source: BoxDynError },
+
+    #[error("error listing the word types of a language: {source}")]
+    ListLanguageWordTypes { source: BoxDynError },
+
+    #[error("error listing languages: {source}")]
+    ListLanguages { source: BoxDynError },
+
     #[error("error expiring all passwords: {source}")]
     ExpireAllPasswords { source: BoxDynError },
 
     #[error("error expiring all sessions: {source}")]
     ExpireAllSessions { source: BoxDynError },
 
+    #[error("error reactivating an account: {source}")]
+    ReactivateAccount { source: BoxDynError },
+
     #[error("error reading password from stdin: {source}")]
     ReadPasswordFromStdin { source: BoxDynError },
 
     #[error("error deleting all user sessions: {source}")]
     DeleteAllUserSessions { source: BoxDynError },
 
+    #[error("error setting word note: {source}")]
+    SetWordNote { source: BoxDynError },
+
+    #[error("error getting word note: {source}")]
+    GetWordNote { source: BoxDynError },
+
+    #[error("error deleting word note: {source}")]
+    DeleteWordNote { source: BoxDynError },
+
+    #[error("error reporting word: {source}")]
+    ReportWord { source: BoxDynError },
+
+    #[error("error listing word reports: {source}")]
+    ListWordReports { source: BoxDynError },
+
+    #[error("error resolving word report: {source}")]
+    ResolveWordReport { source: BoxDynError },
+
     #[error("error logging in: {source}")]
     Login { source: BoxDynError },
 
     #[error("error while inserting a session to the database: {source}")]
     InsertSession { source: BoxDynError },
 
+    #[error("could not generate a session id that does not exist yet")]
+    NoFreeSessionId,
+
     #[error("error while reading a session from the database: {source}")]
     ReadSession { source: BoxDynError },
 
@@ -117,12 +264,27 @@ pub enum RVocError {
     #[error("error while removing all sessiona from the database: {source}")]
     DeleteAllSessions { source: BoxDynError },
 
+    #[error("error reading the dev mode password pepper from {path:?}: {source}")]
+    ReadDevPasswordPepper { path: PathBuf, source: BoxDynError },
+
+    #[error("error writing the dev mode password pepper to {path:?}: {source}")]
+    WriteDevPasswordPepper { path: PathBuf, source: BoxDynError },
+
     #[error("data directory should be a directory, but is a file: {path:?}")]
     DataDirectoryIsFile { path: PathBuf },
 
     #[error("error creating directory {path:?}: {source}")]
     CreateDirectory { path: PathBuf, source: BoxDynError },
 
+    #[error("wiktionary dump file does not exist: {path:?}")]
+    WiktionaryDumpFileDoesNotExist { path: PathBuf },
+
+    #[error("wiktionary dump file should have the extension '.bz2': {path:?}")]
+    WiktionaryDumpFileExtension { path: PathBuf },
+
+    #[error("another wiktionary update is in progress, either started manually or by the web API")]
+    WiktionaryUpdateInProgress,
+
     #[error("error downloading wiktionary dump: {source}")]
     DownloadWiktionaryDump { source: BoxDynError },
 
@@ -151,6 +313,9 @@ pub enum UserError {
         maximum: usize,
     },
 
+    #[error("the password appears in a data breach, please choose a different one")]
+    CompromisedPassword,
+
     #[error("username length ({actual}) outside of allowed range [{minimum}, {maximum}]")]
     UsernameLength {
         actual: usize,
@@ -166,6 +331,60 @@ pub enum UserError {
 
     #[error("the username or password did not match")]
     InvalidUsernamePassword,
+
+    #[error("the account was expired because of inactivity, please contact an administrator")]
+    AccountExpired,
+
+    #[error("this action requires logging in with a password, an api token is not sufficient")]
+    SessionAuthenticationRequired,
+
+    #[error("the route does not exist: {path}")]
+    RouteDoesNotExist { path: String },
+
+    #[error("requests from this origin are not allowed: {origin}")]
+    OriginNotAllowed { origin: String },
+
+    #[error("the request body is malformed: {message}")]
+    MalformedJson { message: String },
+
+    #[error("api token label length ({actual}) is larger than the maximum ({maximum})")]
+    ApiTokenLabelLength { actual: usize, maximum: usize },
+
+    #[error("the api token does not exist: {id}")]
+    ApiTokenDoesNotExist { id: i32 },
+
+    #[error("word note length ({actual}) is larger than the maximum ({maximum})")]
+    WordNoteLength { actual: usize, maximum: usize },
+
+    #[error("the maximum number of word notes ({maximum}) is reached")]
+    WordNoteQuotaExceeded { maximum: usize },
+
+    #[error("word report reason length ({actual}) is larger than the maximum ({maximum})")]
+    WordReportReasonLength { actual: usize, maximum: usize },
+
+    #[error("the maximum number of unresolved word reports ({maximum}) is reached")]
+    WordReportQuotaExceeded { maximum: usize },
+
+    #[error("the word report does not exist or is resolved already: {id}")]
+    WordReportDoesNotExist { id: i32 },
+
+    #[error("requested number of common words ({actual}) is larger than the maximum ({maximum})")]
+    CommonWordsLimit { actual: usize, maximum: usize },
+
+    #[error("number of words in batch ({actual}) is larger than the maximum ({maximum})")]
+    WordBatchSize { actual: usize, maximum: usize },
+
+    #[error("word length ({actual}) is larger than the maximum ({maximum})")]
+    WordLength { actual: usize, maximum: usize },
+
+    #[error("the language does not exist: {language}")]
+    LanguageDoesNotExist { language: String },
+
+    #[error("the word does not exist: {word:?}")]
+    WordDoesNotExist { word: WordIdentifier },
+
+    #[error("the word has no note: {word:?}")]
+    WordNoteDoesNotExist { word: WordIdentifier },
 }
 
 #[allow(dead_code)]
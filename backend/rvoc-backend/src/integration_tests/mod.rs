@@ -1,15 +1,45 @@
+use std::sync::Mutex;
 use std::time::Duration;
 
-use tokio::time::sleep;
+use tokio::time::{sleep, Instant};
 use tracing::{info, instrument};
 
 use crate::configuration::Configuration;
-use crate::database::create_async_database_connection_pool;
-use crate::error::{RVocError, RVocResult};
+use crate::database::advisory_lock::{AdvisoryLock, AdvisoryLockKey};
+use crate::database::api_tokens::{find_api_token_user, hash_api_token};
+use crate::database::frequency_ranks::import_frequency_list;
+use crate::database::password_pepper_canary::check_password_pepper_canary;
+use crate::database::seed::seed_default_languages;
+use crate::database::user_sessions::delete_sessions_of_user_in_batches;
+use crate::database::word_reports::{list_word_reports, resolve_word_report};
+use crate::database::{create_async_database_connection_pool, RVocAsyncDatabaseConnectionPool};
+use crate::error::{RVocError, RVocResult, UserError};
+use crate::job_queue::jobs::expire_inactive_accounts::{
+    expire_inactive_accounts, reactivate_account,
+};
+use crate::job_queue::jobs::refresh_word_stats::refresh_word_stats;
+use crate::job_queue::jobs::update_witkionary::{
+    run_update_wiktionary, run_update_wiktionary_from_file,
+};
+use crate::job_queue::{poll_job_queue_and_execute, JobName};
 
 #[instrument(err, skip(configuration))]
 pub async fn run_internal_integration_tests(configuration: &Configuration) -> RVocResult<()> {
-    test_aborted_transaction(configuration).await
+    test_aborted_transaction(configuration).await?;
+    test_transaction_retry_backoff(configuration).await?;
+    test_refresh_word_stats(configuration).await?;
+    test_expire_inactive_accounts(configuration).await?;
+    test_api_token_of_expired_account(configuration).await?;
+    test_seed_default_languages(configuration).await?;
+    test_update_wiktionary_lock(configuration).await?;
+    test_update_wiktionary_from_file(configuration).await?;
+    test_import_frequency_list(configuration).await?;
+    test_password_pepper_canary(configuration).await?;
+    test_word_reports(configuration).await?;
+    test_delete_sessions_of_user_in_batches(configuration).await?;
+    test_job_name_constraint(configuration).await?;
+    test_disabled_job_is_completed(configuration).await?;
+    test_statement_timeout(configuration).await
 }
 
 #[instrument(err, skip(configuration))]
@@ -17,29 +47,7 @@ async fn test_aborted_transaction(configuration: &Configuration) -> RVocResult<(
     let database_connection_pool = create_async_database_connection_pool(configuration).await?;
 
     // Set up test table
-    database_connection_pool
-        .execute_transaction::<_, RVocError>(
-            |database_connection| {
-                Box::pin(async move {
-                    use crate::database::schema::test_can_be_safely_dropped_in_production::dsl::*;
-                    use diesel::ExpressionMethods;
-                    use diesel_async::RunQueryDsl;
-
-                    diesel::delete(test_can_be_safely_dropped_in_production)
-                        .filter(id.eq_any([1, 2]))
-                        .execute(database_connection)
-                        .await?;
-                    diesel::insert_into(test_can_be_safely_dropped_in_production)
-                        .values([(id.eq(1), name.eq("Tim")), (id.eq(2), name.eq("Tom"))])
-                        .execute(database_connection)
-                        .await?;
-
-                    Ok(())
-                })
-            },
-            0,
-        )
-        .await?;
+    reset_test_table(&database_connection_pool).await?;
 
     info!("Test table set up successfully");
 
@@ -147,3 +155,981 @@ async fn test_aborted_transaction(configuration: &Configuration) -> RVocResult<(
 
     Ok(())
 }
+
+/// Like [`test_aborted_transaction`], but with retries, such that both transactions eventually succeed.
+#[instrument(err, skip(configuration))]
+async fn test_transaction_retry_backoff(configuration: &Configuration) -> RVocResult<()> {
+    let mut configuration = configuration.clone();
+    configuration.transaction_retry_base_delay = chrono::Duration::try_milliseconds(400).unwrap();
+    configuration.transaction_retry_max_delay = chrono::Duration::try_seconds(2).unwrap();
+    let configuration = &configuration;
+    let database_connection_pool = &create_async_database_connection_pool(configuration).await?;
+    let transaction_duration = Duration::from_millis(500);
+
+    // Copy the name of one test row to the other, recording the start time of each attempt.
+    let copy_name = |from: i32, to: i32| {
+        let attempts = Mutex::new(Vec::new());
+        async move {
+            database_connection_pool
+                .execute_transaction::<_, RVocError>(
+                    |database_connection| {
+                        attempts.lock().unwrap().push(Instant::now());
+                        Box::pin(async move {
+                            use crate::database::schema::test_can_be_safely_dropped_in_production::dsl::*;
+                            use diesel::ExpressionMethods;
+                            use diesel::QueryDsl;
+                            use diesel_async::RunQueryDsl;
+
+                            let from_name: Option<String> = test_can_be_safely_dropped_in_production
+                                .select(name)
+                                .filter(id.eq(from))
+                                .first(database_connection)
+                                .await?;
+
+                            sleep(transaction_duration).await;
+
+                            diesel::update(test_can_be_safely_dropped_in_production)
+                                .filter(id.eq(to))
+                                .set(name.eq(from_name))
+                                .execute(database_connection)
+                                .await?;
+
+                            Ok(())
+                        })
+                    },
+                    5,
+                )
+                .await?;
+            RVocResult::Ok(attempts.into_inner().unwrap())
+        }
+    };
+
+    reset_test_table(database_connection_pool).await?;
+    let (first, second) = tokio::join!(copy_name(1, 2), copy_name(2, 1));
+    info!("First attempts:  {first:?}");
+    info!("Second attempts: {second:?}");
+    let (first, second) = (first?, second?);
+    assert!(first.len() > 1 || second.len() > 1);
+
+    // Each retry waits at least half of the base delay after the failed attempt.
+    for attempts in [first, second] {
+        for window in attempts.windows(2) {
+            assert!(window[1] - window[0] >= transaction_duration + Duration::from_millis(200));
+        }
+    }
+
+    reset_test_table(database_connection_pool).await?;
+
+    info!("Success! Conflicting transactions are retried with a delay");
+
+    Ok(())
+}
+
+/// Insert the rows (1, "Tim") and (2, "Tom") into the test table, replacing any existing rows with these ids.
+async fn reset_test_table(
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+) -> RVocResult<()> {
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::test_can_be_safely_dropped_in_production::dsl::*;
+                    use diesel::ExpressionMethods;
+                    use diesel_async::RunQueryDsl;
+
+                    diesel::delete(test_can_be_safely_dropped_in_production)
+                        .filter(id.eq_any([1, 2]))
+                        .execute(database_connection)
+                        .await?;
+                    diesel::insert_into(test_can_be_safely_dropped_in_production)
+                        .values([(id.eq(1), name.eq("Tim")), (id.eq(2), name.eq("Tom"))])
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(())
+                })
+            },
+            0,
+        )
+        .await
+}
+
+#[instrument(err, skip(configuration))]
+async fn test_refresh_word_stats(configuration: &Configuration) -> RVocResult<()> {
+    let database_connection_pool = create_async_database_connection_pool(configuration).await?;
+
+    refresh_word_stats(&database_connection_pool, configuration).await?;
+
+    let (precomputed, direct) = database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::words;
+                    use crate::database::views::language_word_counts;
+                    use diesel::dsl::count_star;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let precomputed: Vec<(i32, i64)> = language_word_counts::table
+                        .select((
+                            language_word_counts::language,
+                            language_word_counts::word_count,
+                        ))
+                        .order_by(language_word_counts::language)
+                        .load(database_connection)
+                        .await?;
+                    let direct: Vec<(i32, i64)> = words::table
+                        .group_by(words::language)
+                        .select((words::language, count_star()))
+                        .order_by(words::language)
+                        .load(database_connection)
+                        .await?;
+
+                    Ok((precomputed, direct))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+
+    info!("Precomputed word counts: {precomputed:?}");
+    info!("Directly computed word counts: {direct:?}");
+    assert_eq!(precomputed, direct);
+
+    info!("Success! Word stats match the words table after a refresh");
+
+    Ok(())
+}
+
+#[instrument(err, skip(configuration))]
+async fn test_expire_inactive_accounts(configuration: &Configuration) -> RVocResult<()> {
+    let database_connection_pool = create_async_database_connection_pool(configuration).await?;
+    let mut configuration = configuration.clone();
+    configuration.account_inactivity_expiry = Some(chrono::Duration::try_days(30).unwrap());
+    let configuration = &configuration;
+
+    // Set up test users
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::users;
+                    use chrono::Utc;
+                    use diesel::ExpressionMethods;
+                    use diesel_async::RunQueryDsl;
+
+                    diesel::delete(users::table)
+                        .filter(users::name.eq_any(["inactive_test_user", "active_test_user"]))
+                        .execute(database_connection)
+                        .await?;
+                    diesel::insert_into(users::table)
+                        .values([
+                            (
+                                users::name.eq("inactive_test_user"),
+                                users::password_hash.eq(Some("not a real hash")),
+                                users::last_login_at
+                                    .eq(Utc::now() - chrono::Duration::try_days(31).unwrap()),
+                            ),
+                            (
+                                users::name.eq("active_test_user"),
+                                users::password_hash.eq(Some("not a real hash")),
+                                users::last_login_at
+                                    .eq(Utc::now() - chrono::Duration::try_days(29).unwrap()),
+                            ),
+                        ])
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(())
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+
+    expire_inactive_accounts(&database_connection_pool, configuration).await?;
+
+    let load_users = || {
+        database_connection_pool.execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::users;
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let users: Vec<(String, Option<String>, bool)> = users::table
+                        .select((
+                            users::name,
+                            users::password_hash,
+                            users::expired_at.is_not_null(),
+                        ))
+                        .filter(users::name.eq_any(["inactive_test_user", "active_test_user"]))
+                        .order_by(users::name)
+                        .load(database_connection)
+                        .await?;
+
+                    Ok(users)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+    };
+
+    // Only the inactive account is expired, and the password hashes are kept
+    assert_eq!(
+        load_users().await?,
+        [
+            (
+                "active_test_user".to_owned(),
+                Some("not a real hash".to_owned()),
+                false
+            ),
+            (
+                "inactive_test_user".to_owned(),
+                Some("not a real hash".to_owned()),
+                true
+            ),
+        ]
+    );
+
+    // A reactivated account is not expired again by the next run
+    reactivate_account(
+        "inactive_test_user",
+        &database_connection_pool,
+        configuration,
+    )
+    .await?;
+    expire_inactive_accounts(&database_connection_pool, configuration).await?;
+    assert!(load_users()
+        .await?
+        .iter()
+        .all(|(_, _, is_expired)| !is_expired));
+
+    info!("Success! Only the inactive account was expired, and it can be reactivated");
+
+    Ok(())
+}
+
+/// The authentication middleware answers with `401 Unauthorized` if [`find_api_token_user`] returns `None`.
+#[instrument(err, skip(configuration))]
+async fn test_api_token_of_expired_account(configuration: &Configuration) -> RVocResult<()> {
+    let database_connection_pool = create_async_database_connection_pool(configuration).await?;
+    let mut configuration = configuration.clone();
+    configuration.account_inactivity_expiry = Some(chrono::Duration::try_days(30).unwrap());
+    let configuration = &configuration;
+
+    const USERNAME: &str = "api_token_test_user";
+    const TOKEN: &str = "api token test token";
+
+    let set_last_login_at = |days_ago| {
+        database_connection_pool.execute_transaction::<_, RVocError>(
+            move |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::users;
+                    use chrono::Utc;
+                    use diesel::ExpressionMethods;
+                    use diesel_async::RunQueryDsl;
+
+                    diesel::update(users::table)
+                        .filter(users::name.eq(USERNAME))
+                        .set(
+                            users::last_login_at
+                                .eq(Utc::now() - chrono::Duration::try_days(days_ago).unwrap()),
+                        )
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(())
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+    };
+
+    // Set up test user with an API token
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::{api_tokens, users};
+                    use chrono::Utc;
+                    use diesel::ExpressionMethods;
+                    use diesel_async::RunQueryDsl;
+
+                    diesel::delete(users::table)
+                        .filter(users::name.eq(USERNAME))
+                        .execute(database_connection)
+                        .await?;
+                    diesel::insert_into(users::table)
+                        .values((
+                            users::name.eq(USERNAME),
+                            users::password_hash.eq(Some("not a real hash")),
+                            users::last_login_at.eq(Utc::now()),
+                        ))
+                        .execute(database_connection)
+                        .await?;
+                    diesel::insert_into(api_tokens::table)
+                        .values((
+                            api_tokens::username.eq(USERNAME),
+                            api_tokens::token_hash.eq(hash_api_token(TOKEN)),
+                            api_tokens::label.eq("test token"),
+                        ))
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(())
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+
+    // Using the token counts as a login
+    set_last_login_at(31).await?;
+    let username = find_api_token_user(TOKEN, &database_connection_pool, configuration).await?;
+    assert_eq!(username.as_ref().map(AsRef::<str>::as_ref), Some(USERNAME));
+    expire_inactive_accounts(&database_connection_pool, configuration).await?;
+    let username = find_api_token_user(TOKEN, &database_connection_pool, configuration).await?;
+    assert_eq!(username.as_ref().map(AsRef::<str>::as_ref), Some(USERNAME));
+
+    // The token of an expired account is rejected, and it is deleted with the expiry
+    set_last_login_at(31).await?;
+    expire_inactive_accounts(&database_connection_pool, configuration).await?;
+    let username = find_api_token_user(TOKEN, &database_connection_pool, configuration).await?;
+    assert!(username.is_none());
+
+    let remaining_api_tokens = database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::api_tokens;
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let remaining_api_tokens: i64 = api_tokens::table
+                        .filter(api_tokens::username.eq(USERNAME))
+                        .count()
+                        .get_result(database_connection)
+                        .await?;
+
+                    Ok(remaining_api_tokens)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+    assert_eq!(remaining_api_tokens, 0);
+
+    info!("Success! Using an API token counts as a login, and the tokens of expired accounts are rejected and deleted");
+
+    Ok(())
+}
+
+#[instrument(err, skip(configuration))]
+async fn test_seed_default_languages(configuration: &Configuration) -> RVocResult<()> {
+    let database_connection_pool = create_async_database_connection_pool(configuration).await?;
+    let mut configuration = configuration.clone();
+    configuration.default_languages = vec![
+        "Seeded Test Language A".to_owned(),
+        "Seeded Test Language B".to_owned(),
+    ];
+    let configuration = &configuration;
+
+    seed_default_languages(&database_connection_pool, configuration).await?;
+    seed_default_languages(&database_connection_pool, configuration).await?;
+
+    let seeded_languages = database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::languages;
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel::TextExpressionMethods;
+                    use diesel_async::RunQueryDsl;
+
+                    let seeded_languages: Vec<String> = languages::table
+                        .select(languages::english_name)
+                        .filter(languages::english_name.like("Seeded Test Language %"))
+                        .order_by(languages::english_name)
+                        .load(database_connection)
+                        .await?;
+
+                    Ok(seeded_languages)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+
+    assert_eq!(seeded_languages, configuration.default_languages);
+
+    info!("Success! Seeding default languages twice produces each language exactly once");
+
+    Ok(())
+}
+
+#[instrument(err, skip(configuration))]
+async fn test_update_wiktionary_lock(configuration: &Configuration) -> RVocResult<()> {
+    let database_connection_pool = create_async_database_connection_pool(configuration).await?;
+
+    let lock = AdvisoryLock::try_acquire(AdvisoryLockKey::UpdateWiktionary, configuration)
+        .await?
+        .expect("the wiktionary update lock should be free");
+    assert!(
+        AdvisoryLock::try_acquire(AdvisoryLockKey::UpdateWiktionary, configuration)
+            .await?
+            .is_none()
+    );
+
+    // The update fails before downloading anything, since the lock is checked first.
+    assert!(matches!(
+        run_update_wiktionary(&database_connection_pool, configuration).await,
+        Err(RVocError::WiktionaryUpdateInProgress)
+    ));
+
+    drop(lock);
+    assert!(
+        AdvisoryLock::try_acquire(AdvisoryLockKey::UpdateWiktionary, configuration)
+            .await?
+            .is_some()
+    );
+
+    info!("Success! A second simultaneous wiktionary update is rejected");
+
+    Ok(())
+}
+
+/// A tiny wiktionary dump with the Esperanto noun "domo" and the Esperanto verb "kuri".
+/// It is embedded into the binary, since the internal integration tests do not run from the source directory.
+const WIKTIONARY_FIXTURE_DUMP: &[u8] =
+    include_bytes!("../../test-data/enwiktionary-fixture-pages-articles.xml.bz2");
+
+#[instrument(err, skip(configuration))]
+async fn test_update_wiktionary_from_file(configuration: &Configuration) -> RVocResult<()> {
+    let database_connection_pool = create_async_database_connection_pool(configuration).await?;
+
+    let dump_file = std::env::temp_dir().join(format!(
+        "rvoc-{}-pages-articles.xml.bz2",
+        std::process::id()
+    ));
+    std::fs::write(&dump_file, WIKTIONARY_FIXTURE_DUMP).unwrap();
+    let result = run_update_wiktionary_from_file(
+        dump_file.clone(),
+        &database_connection_pool,
+        configuration,
+    )
+    .await;
+    std::fs::remove_file(&dump_file).unwrap();
+    result?;
+
+    let imported_words = database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::{languages, word_types, words};
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let imported_words: Vec<(String, String)> = words::table
+                        .inner_join(languages::table)
+                        .inner_join(word_types::table)
+                        .select((words::word, word_types::english_name))
+                        .filter(languages::english_name.eq("Esperanto"))
+                        .filter(words::word.eq_any(["domo", "kuri"]))
+                        .order_by(words::word)
+                        .load(database_connection)
+                        .await?;
+
+                    Ok(imported_words)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+
+    assert_eq!(
+        imported_words,
+        [
+            ("domo".to_owned(), "Noun".to_owned()),
+            ("kuri".to_owned(), "Verb".to_owned()),
+        ]
+    );
+
+    info!("Success! The words of a local wiktionary dump file are imported");
+
+    Ok(())
+}
+
+/// Relies on the words imported by [`test_update_wiktionary_from_file`].
+#[instrument(err, skip(configuration))]
+async fn test_import_frequency_list(configuration: &Configuration) -> RVocResult<()> {
+    let database_connection_pool = create_async_database_connection_pool(configuration).await?;
+
+    let frequency_list_file =
+        std::env::temp_dir().join(format!("rvoc-{}-frequency-list.csv", std::process::id()));
+    std::fs::write(&frequency_list_file, "domo, 7\nkuri,3\n\nnot a word,1\n").unwrap();
+    let result = import_frequency_list(
+        "Esperanto",
+        &frequency_list_file,
+        &database_connection_pool,
+        configuration,
+    )
+    .await;
+    std::fs::remove_file(&frequency_list_file).unwrap();
+    assert_eq!(result?, 2);
+
+    let frequency_ranks = database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::{languages, words};
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let frequency_ranks: Vec<(String, Option<i32>)> = words::table
+                        .inner_join(languages::table)
+                        .select((words::word, words::frequency_rank))
+                        .filter(languages::english_name.eq("Esperanto"))
+                        .filter(words::word.eq_any(["domo", "kuri"]))
+                        .order_by(words::word)
+                        .load(database_connection)
+                        .await?;
+
+                    Ok(frequency_ranks)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+
+    assert_eq!(
+        frequency_ranks,
+        [("domo".to_owned(), Some(7)), ("kuri".to_owned(), Some(3))]
+    );
+
+    info!("Success! The frequency ranks of a frequency list are imported");
+
+    Ok(())
+}
+
+#[instrument(err, skip(configuration))]
+async fn test_word_reports(configuration: &Configuration) -> RVocResult<()> {
+    let database_connection_pool = &create_async_database_connection_pool(configuration).await?;
+
+    // Set up a test user with a report
+    let report_id = database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::{users, word_reports};
+                    use diesel::ExpressionMethods;
+                    use diesel_async::RunQueryDsl;
+
+                    diesel::delete(users::table)
+                        .filter(users::name.eq("word_report_test_user"))
+                        .execute(database_connection)
+                        .await?;
+                    diesel::insert_into(users::table)
+                        .values((
+                            users::name.eq("word_report_test_user"),
+                            users::password_hash.eq(Some("not a real hash")),
+                        ))
+                        .execute(database_connection)
+                        .await?;
+                    let report_id: i32 = diesel::insert_into(word_reports::table)
+                        .values((
+                            word_reports::username.eq("word_report_test_user"),
+                            word_reports::language.eq("English"),
+                            word_reports::word.eq("house"),
+                            word_reports::word_type.eq("Noun"),
+                            word_reports::reason.eq("test"),
+                        ))
+                        .returning(word_reports::id)
+                        .get_result(database_connection)
+                        .await?;
+
+                    Ok(report_id)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+
+    let is_listed = |include_resolved| async move {
+        RVocResult::Ok(
+            list_word_reports(include_resolved, database_connection_pool, configuration)
+                .await?
+                .iter()
+                .any(|word_report| word_report.id == report_id),
+        )
+    };
+
+    assert!(is_listed(false).await?);
+    resolve_word_report(report_id, database_connection_pool, configuration).await?;
+    assert!(!is_listed(false).await?);
+    assert!(is_listed(true).await?);
+
+    // A resolved report cannot be resolved again
+    assert!(matches!(
+        resolve_word_report(report_id, database_connection_pool, configuration).await,
+        Err(RVocError::UserError(
+            UserError::WordReportDoesNotExist { .. }
+        ))
+    ));
+
+    // Clean up, which deletes the report as well
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::users;
+                    use diesel::ExpressionMethods;
+                    use diesel_async::RunQueryDsl;
+
+                    diesel::delete(users::table)
+                        .filter(users::name.eq("word_report_test_user"))
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(())
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+
+    info!("Success! Word reports can be listed and resolved");
+
+    Ok(())
+}
+
+#[instrument(err, skip(configuration))]
+async fn test_delete_sessions_of_user_in_batches(configuration: &Configuration) -> RVocResult<()> {
+    let mut configuration = configuration.clone();
+    configuration.session_delete_batch_size = 10;
+    let configuration = &configuration;
+    let database_connection_pool = &create_async_database_connection_pool(configuration).await?;
+    let session_count = 25;
+
+    // Set up a test user with more sessions than fit into a batch
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::{sessions, users};
+                    use diesel::dsl::now;
+                    use diesel::ExpressionMethods;
+                    use diesel_async::RunQueryDsl;
+
+                    diesel::delete(users::table)
+                        .filter(users::name.eq("session_delete_test_user"))
+                        .execute(database_connection)
+                        .await?;
+                    diesel::insert_into(users::table)
+                        .values((
+                            users::name.eq("session_delete_test_user"),
+                            users::password_hash.eq(Some("not a real hash")),
+                        ))
+                        .execute(database_connection)
+                        .await?;
+                    diesel::insert_into(sessions::table)
+                        .values(
+                            (0..session_count)
+                                .map(|index| {
+                                    (
+                                        sessions::id
+                                            .eq(format!("session_delete_test_{index}").into_bytes()),
+                                        sessions::expiry.eq(now),
+                                        sessions::username.eq("session_delete_test_user"),
+                                    )
+                                })
+                                .collect::<Vec<_>>(),
+                        )
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(())
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+
+    assert_eq!(
+        delete_sessions_of_user_in_batches(
+            "session_delete_test_user",
+            database_connection_pool,
+            configuration
+        )
+        .await?,
+        session_count
+    );
+
+    // Check that no sessions are left, and clean up
+    let remaining_session_count = database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::{sessions, users};
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let remaining_session_count: i64 = sessions::table
+                        .filter(sessions::username.eq("session_delete_test_user"))
+                        .count()
+                        .get_result(database_connection)
+                        .await?;
+                    diesel::delete(users::table)
+                        .filter(users::name.eq("session_delete_test_user"))
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(remaining_session_count)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+    assert_eq!(remaining_session_count, 0);
+
+    info!("Success! All sessions of a user are deleted in batches");
+
+    Ok(())
+}
+
+#[instrument(err, skip(configuration))]
+async fn test_job_name_constraint(configuration: &Configuration) -> RVocResult<()> {
+    use strum::IntoEnumIterator;
+
+    let database_connection_pool = &create_async_database_connection_pool(configuration).await?;
+    let insert_job = |job_name: String| {
+        database_connection_pool.execute_transaction::<_, RVocError>(
+            move |database_connection| {
+                let job_name = job_name.clone();
+                Box::pin(async move {
+                    use crate::database::schema::job_queue::dsl::*;
+                    use diesel::{dsl::now, ExpressionMethods};
+                    use diesel_async::RunQueryDsl;
+
+                    diesel::insert_into(job_queue)
+                        .values((
+                            name.eq(job_name),
+                            scheduled_execution_time.eq(now),
+                            in_progress.eq(false),
+                        ))
+                        .on_conflict_do_nothing()
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(())
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+    };
+
+    // All known jobs are accepted, so the constraint is in sync with the code
+    for job_name in JobName::iter() {
+        insert_job(job_name.to_string()).await?;
+    }
+
+    let Err(RVocError::PermanentDatabaseTransactionError { source }) =
+        insert_job("NoSuchJob".to_owned()).await
+    else {
+        panic!("Inserting a job with an unknown name did not fail");
+    };
+    assert!(
+        matches!(
+            source.downcast_ref::<diesel::result::Error>(),
+            Some(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::CheckViolation,
+                _
+            ))
+        ),
+        "Unexpected error: {source}"
+    );
+
+    info!("Success! The job queue only accepts known job names");
+
+    Ok(())
+}
+
+#[instrument(err, skip(configuration))]
+async fn test_disabled_job_is_completed(configuration: &Configuration) -> RVocResult<()> {
+    let mut configuration = configuration.clone();
+    configuration.integration_test_mode = true;
+    // If the job was run, it would fail right away instead of downloading anything,
+    // since no directory can be created below a regular file.
+    configuration.wiktionary_temporary_data_directory =
+        std::env::current_exe().unwrap().join("wiktionary-data");
+    configuration.maximum_wiktionary_update_retry_count = 0;
+    let configuration = &configuration;
+    assert!(!configuration.should_run_job(JobName::UpdateWiktionary));
+
+    let database_connection_pool = &create_async_database_connection_pool(configuration).await?;
+
+    // Schedule the job before all other jobs, such that it is the next one to be executed
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::job_queue::dsl::*;
+                    use chrono::{DateTime, Utc};
+                    use diesel::ExpressionMethods;
+                    use diesel_async::RunQueryDsl;
+
+                    let first_scheduled_execution_time =
+                        DateTime::<Utc>::from_timestamp(0, 0).unwrap();
+                    diesel::insert_into(job_queue)
+                        .values((
+                            name.eq(JobName::UpdateWiktionary.as_ref()),
+                            scheduled_execution_time.eq(first_scheduled_execution_time),
+                            in_progress.eq(false),
+                        ))
+                        .on_conflict(name)
+                        .do_update()
+                        .set((
+                            scheduled_execution_time.eq(first_scheduled_execution_time),
+                            in_progress.eq(false),
+                        ))
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(())
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+
+    let poll_time = chrono::Utc::now();
+    poll_job_queue_and_execute(database_connection_pool, configuration).await?;
+
+    let (next_execution_time, is_in_progress) = database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::job_queue::dsl::*;
+                    use chrono::{DateTime, Utc};
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let scheduled_job: (DateTime<Utc>, bool) = job_queue
+                        .select((scheduled_execution_time, in_progress))
+                        .filter(name.eq(JobName::UpdateWiktionary.as_ref()))
+                        .first(database_connection)
+                        .await?;
+
+                    Ok(scheduled_job)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+
+    assert!(!is_in_progress);
+    assert!(
+        next_execution_time >= poll_time + configuration.wiktionary_update_interval,
+        "The disabled job was not rescheduled: {next_execution_time}"
+    );
+
+    info!("Success! A disabled job is completed without running it");
+
+    Ok(())
+}
+
+#[instrument(err, skip(configuration))]
+async fn test_password_pepper_canary(configuration: &Configuration) -> RVocResult<()> {
+    let database_connection_pool = &create_async_database_connection_pool(configuration).await?;
+    let delete_canary = || async move {
+        database_connection_pool
+            .execute_transaction::<_, RVocError>(
+                |database_connection| {
+                    Box::pin(async move {
+                        use crate::database::schema::settings;
+                        use diesel::ExpressionMethods;
+                        use diesel_async::RunQueryDsl;
+
+                        diesel::delete(settings::table)
+                            .filter(settings::name.eq("password_pepper_canary"))
+                            .execute(database_connection)
+                            .await?;
+
+                        Ok(())
+                    })
+                },
+                configuration.maximum_transaction_retry_count,
+            )
+            .await
+    };
+
+    let mut old_configuration = configuration.clone();
+    old_configuration.password_pepper = "canaryoldpepper".into();
+    let mut new_configuration = configuration.clone();
+    new_configuration.password_pepper = "canarynewpepper".into();
+
+    delete_canary().await?;
+
+    // the first check creates the canary
+    assert!(check_password_pepper_canary(database_connection_pool, &old_configuration).await?);
+    assert!(check_password_pepper_canary(database_connection_pool, &old_configuration).await?);
+
+    // changing the pepper is detected
+    assert!(!check_password_pepper_canary(database_connection_pool, &new_configuration).await?);
+
+    // rotating the pepper is not
+    new_configuration.previous_password_peppers = vec!["canaryoldpepper".into()];
+    assert!(check_password_pepper_canary(database_connection_pool, &new_configuration).await?);
+
+    // and after the rotation, the canary uses the new pepper
+    new_configuration.previous_password_peppers = Vec::new();
+    assert!(check_password_pepper_canary(database_connection_pool, &new_configuration).await?);
+    assert!(!check_password_pepper_canary(database_connection_pool, &old_configuration).await?);
+
+    // Do not leave a canary with a test pepper behind.
+    delete_canary().await?;
+
+    info!("Success! Changing the password pepper is detected by the canary");
+
+    Ok(())
+}
+
+#[instrument(err, skip(configuration))]
+async fn test_statement_timeout(configuration: &Configuration) -> RVocResult<()> {
+    let mut configuration = configuration.clone();
+    configuration.database_statement_timeout =
+        Some(chrono::Duration::try_milliseconds(100).unwrap());
+    let configuration = &configuration;
+    let database_connection_pool = create_async_database_connection_pool(configuration).await?;
+
+    let sleep_in_database = |seconds: f64| {
+        database_connection_pool.execute_transaction::<_, RVocError>(
+            move |database_connection| {
+                Box::pin(async move {
+                    use diesel_async::RunQueryDsl;
+
+                    diesel::sql_query(format!("SELECT pg_sleep({seconds})"))
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(())
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+    };
+
+    sleep_in_database(0.01).await?;
+    assert!(sleep_in_database(1.0).await.is_err());
+
+    info!("Success! Slow statements are aborted by the statement timeout");
+
+    Ok(())
+}
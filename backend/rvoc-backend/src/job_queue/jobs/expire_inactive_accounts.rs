@@ -0,0 +1,118 @@
+use chrono::Utc;
+use tracing::{info, instrument};
+
+use crate::{
+    configuration::Configuration,
+    database::RVocAsyncDatabaseConnectionPool,
+    error::{RVocError, RVocResult, UserError},
+};
+
+/// Expire the accounts of all users who have not logged in for longer than the configured inactivity threshold.
+/// Expired accounts are marked with an expiry time, which prevents logging in, and their sessions and API tokens are deleted.
+/// The data of the users is kept, and an operator can reactivate an account with [`reactivate_account`].
+pub async fn expire_inactive_accounts(
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+    configuration: &Configuration,
+) -> RVocResult<()> {
+    let Some(account_inactivity_expiry) = configuration.account_inactivity_expiry else {
+        return Ok(());
+    };
+    let last_login_cutoff = Utc::now() - account_inactivity_expiry;
+
+    let expired_usernames = database_connection_pool
+        .execute_read_committed_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::{api_tokens, sessions, users};
+                    use diesel::dsl::now;
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let expired_usernames: Vec<String> = diesel::update(users::table)
+                        .filter(users::last_login_at.lt(last_login_cutoff))
+                        .filter(users::expired_at.is_null())
+                        .set(users::expired_at.eq(now))
+                        .returning(users::name)
+                        .get_results(database_connection)
+                        .await?;
+
+                    diesel::delete(sessions::table)
+                        .filter(sessions::username.eq_any(&expired_usernames))
+                        .execute(database_connection)
+                        .await?;
+
+                    diesel::delete(api_tokens::table)
+                        .filter(api_tokens::username.eq_any(&expired_usernames))
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(expired_usernames)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await?;
+
+    if !expired_usernames.is_empty() {
+        info!(
+            "Expired {} accounts that were inactive since before {last_login_cutoff}",
+            expired_usernames.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Reactivate an account that was expired because of inactivity.
+/// This counts as a login, such that the account does not get expired again right away.
+/// The API tokens that were deleted when the account was expired are not restored.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn reactivate_account(
+    username: &str,
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+    configuration: &Configuration,
+) -> RVocResult<()> {
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async move {
+                    use crate::database::schema::users;
+                    use diesel::dsl::now;
+                    use diesel::ExpressionMethods;
+                    use diesel_async::RunQueryDsl;
+
+                    match diesel::update(users::table)
+                        .filter(users::name.eq(username))
+                        .set((
+                            users::expired_at.eq(Option::<chrono::DateTime<Utc>>::None),
+                            users::last_login_at.eq(now),
+                        ))
+                        .execute(database_connection)
+                        .await?
+                    {
+                        0 => Err(UserError::UsernameDoesNotExist {
+                            username: username.to_owned(),
+                        }
+                        .into()),
+                        1 => Ok(()),
+                        affected_rows => unreachable!(
+                            "updated exactly one user, but affected {affected_rows} rows"
+                        ),
+                    }
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::ReactivateAccount {
+                source: Box::new(error),
+            },
+        })?;
+
+    info!("Reactivated account {username:?}");
+
+    Ok(())
+}
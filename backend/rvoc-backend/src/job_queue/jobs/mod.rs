@@ -1,2 +1,4 @@
 pub mod delete_expired_sessions;
+pub mod expire_inactive_accounts;
+pub mod refresh_word_stats;
 pub mod update_witkionary;
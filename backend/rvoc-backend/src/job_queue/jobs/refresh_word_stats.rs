@@ -0,0 +1,31 @@
+use crate::{
+    configuration::Configuration,
+    database::RVocAsyncDatabaseConnectionPool,
+    error::{RVocError, RVocResult},
+};
+
+/// Recompute the materialized word statistics.
+pub async fn refresh_word_stats(
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+    configuration: &Configuration,
+) -> RVocResult<()> {
+    // The view is refreshed concurrently, so it stays readable while being refreshed.
+    database_connection_pool
+        .execute_read_committed_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use diesel_async::RunQueryDsl;
+
+                    diesel::sql_query(
+                        "REFRESH MATERIALIZED VIEW CONCURRENTLY language_word_counts",
+                    )
+                    .execute(database_connection)
+                    .await?;
+
+                    Ok(())
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+}
@@ -1,11 +1,19 @@
+use chrono::Duration;
 use tracing::warn;
 
 use crate::{
-    configuration::Configuration, database::RVocAsyncDatabaseConnectionPool, error::RVocError,
+    configuration::Configuration,
+    database::{
+        advisory_lock::{AdvisoryLock, AdvisoryLockKey},
+        RVocAsyncDatabaseConnectionPool,
+    },
+    error::RVocError,
     error::RVocResult,
 };
 
-use std::path::PathBuf;
+use std::ffi::OsStr;
+use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use tokio::fs;
@@ -19,12 +27,50 @@ pub async fn update_wiktionary(
     database_connection_pool: &RVocAsyncDatabaseConnectionPool,
     configuration: &Configuration,
 ) -> RVocResult<()> {
-    if configuration.integration_test_mode {
-        warn!("Not running update_wiktionary because integration_test_mode is enabled");
-        return Ok(());
+    let result = retry_transient_failures(
+        configuration.maximum_wiktionary_update_retry_count,
+        configuration.wiktionary_update_retry_delay,
+        || run_update_wiktionary(database_connection_pool, configuration),
+    )
+    .await;
+
+    match result {
+        // The update was started manually, so skip this scheduled execution.
+        Err(RVocError::WiktionaryUpdateInProgress) => {
+            warn!("Not running update_wiktionary because another update is in progress");
+            Ok(())
+        }
+        result => result,
     }
+}
+
+/// Run `operation` until it succeeds, fails permanently, or fails transiently `maximum_retry_count + 1` times.
+/// The delay between attempts starts at `initial_retry_delay` and doubles after each attempt.
+async fn retry_transient_failures<T, OperationFuture: Future<Output = RVocResult<T>>>(
+    maximum_retry_count: u64,
+    initial_retry_delay: Duration,
+    mut operation: impl FnMut() -> OperationFuture,
+) -> RVocResult<T> {
+    let mut retry_count = 0;
+    let mut retry_delay = initial_retry_delay;
 
-    run_update_wiktionary(database_connection_pool, configuration).await
+    loop {
+        match operation().await {
+            Err(error) if is_transient(&error) && retry_count < maximum_retry_count => {
+                retry_count += 1;
+                warn!("Transient error while updating wiktionary, retrying in {retry_delay} (retry {retry_count} of {maximum_retry_count}): {error}");
+                tokio::time::sleep(retry_delay.to_std().unwrap()).await;
+                retry_delay = retry_delay.checked_mul(2).unwrap_or(retry_delay);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Errors that may go away by themselves, like network errors.
+/// Errors in our own logic or in the data would just repeat, so they are not retried.
+fn is_transient(error: &RVocError) -> bool {
+    matches!(error, RVocError::DownloadWiktionaryDump { .. })
 }
 
 #[instrument(err, skip(database_connection_pool, configuration))]
@@ -34,10 +80,63 @@ pub async fn run_update_wiktionary(
 ) -> RVocResult<()> {
     info!("Updating wiktionary data");
     debug!("Configuration: {configuration:#?}");
+    let _lock = acquire_update_wiktionary_lock(configuration).await?;
 
     let new_dump_file = update_wiktionary_dump_files(configuration).await?;
-    // expect the extension to be ".tar.bz2", and replace it with ".log"
-    let error_log = new_dump_file.with_extension("").with_extension("log");
+    import_wiktionary_dump_file(new_dump_file, database_connection_pool, configuration).await
+}
+
+/// Update the wiktionary data from a local dump file instead of downloading the newest dump.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn run_update_wiktionary_from_file(
+    dump_file: PathBuf,
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+    configuration: &Configuration,
+) -> RVocResult<()> {
+    info!("Updating wiktionary data from local file {dump_file:?}");
+    verify_local_dump_file(&dump_file)?;
+    let _lock = acquire_update_wiktionary_lock(configuration).await?;
+    import_wiktionary_dump_file(dump_file, database_connection_pool, configuration).await
+}
+
+/// Acquire the lock that prevents simultaneous wiktionary updates by the job queue and the cli.
+/// The lock is released when the returned value is dropped.
+async fn acquire_update_wiktionary_lock(configuration: &Configuration) -> RVocResult<AdvisoryLock> {
+    AdvisoryLock::try_acquire(AdvisoryLockKey::UpdateWiktionary, configuration)
+        .await?
+        .ok_or(RVocError::WiktionaryUpdateInProgress)
+}
+
+/// Check that the given path points to a compressed dump file.
+fn verify_local_dump_file(dump_file: &Path) -> RVocResult<()> {
+    if !dump_file.is_file() {
+        return Err(RVocError::WiktionaryDumpFileDoesNotExist {
+            path: dump_file.to_owned(),
+        });
+    }
+
+    if dump_file.extension().and_then(OsStr::to_str) != Some("bz2") {
+        return Err(RVocError::WiktionaryDumpFileExtension {
+            path: dump_file.to_owned(),
+        });
+    }
+
+    Ok(())
+}
+
+async fn import_wiktionary_dump_file(
+    new_dump_file: PathBuf,
+    database_connection_pool: &RVocAsyncDatabaseConnectionPool,
+    configuration: &Configuration,
+) -> RVocResult<()> {
+    // The dump file may have been supplied by the user, so the log is written to our own data directory.
+    // Expect the extension to be ".xml.bz2", and replace it with ".log".
+    let target_directory = create_data_directory(configuration).await?;
+    let error_log = target_directory.join(
+        Path::new(new_dump_file.file_name().unwrap_or_default())
+            .with_extension("")
+            .with_extension("log"),
+    );
 
     // This is a bit laborious, but without proper scoping we cannot pass the buffer
     // to parse_dump_file otherwise.
@@ -88,6 +187,12 @@ async fn insert_word_buffer(
     database_connection_pool: &RVocAsyncDatabaseConnectionPool,
     configuration: &Configuration,
 ) -> Result<(), RVocError> {
+    let discarded_word_count =
+        discard_invalid_words(word_buffer, configuration.maximum_word_length);
+    if discarded_word_count > 0 {
+        info!("Skipping {discarded_word_count} wiktionary words that are empty or too long");
+    }
+
     debug!(
         "Inserting {} wiktionary words into database",
         word_buffer.len()
@@ -175,9 +280,19 @@ async fn insert_word_buffer(
     Ok(())
 }
 
-#[instrument(err, skip(configuration))]
-async fn update_wiktionary_dump_files(configuration: &Configuration) -> RVocResult<PathBuf> {
-    debug!("Updating wiktionary dump files");
+/// Remove words that are empty or consist only of whitespace, as well as words longer than `maximum_word_length`.
+/// The latter could never be queried anyways, since the API rejects them.
+///
+/// Returns the number of removed words.
+fn discard_invalid_words(word_buffer: &mut Vec<Word>, maximum_word_length: usize) -> usize {
+    let original_length = word_buffer.len();
+    word_buffer
+        .retain(|word| !word.word.trim().is_empty() && word.word.len() <= maximum_word_length);
+    original_length - word_buffer.len()
+}
+
+/// Create [`Configuration::wiktionary_temporary_data_directory`] if it does not exist yet.
+async fn create_data_directory(configuration: &Configuration) -> RVocResult<&PathBuf> {
     let target_directory = &configuration.wiktionary_temporary_data_directory;
     if !target_directory.exists() {
         fs::create_dir_all(&target_directory)
@@ -192,6 +307,14 @@ async fn update_wiktionary_dump_files(configuration: &Configuration) -> RVocResu
         });
     }
 
+    Ok(target_directory)
+}
+
+#[instrument(err, skip(configuration))]
+async fn update_wiktionary_dump_files(configuration: &Configuration) -> RVocResult<PathBuf> {
+    debug!("Updating wiktionary dump files");
+    let target_directory = create_data_directory(configuration).await?;
+
     let new_dump_file = wiktionary_dump_parser::download_language(
         &DumpBaseUrl::Default,
         &LanguageCode::English,
@@ -245,3 +368,107 @@ async fn update_wiktionary_dump_files(configuration: &Configuration) -> RVocResu
 
     Ok(new_dump_file)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use chrono::Duration;
+
+    use wiktionary_dump_parser::parser::words::Word;
+
+    use crate::error::RVocError;
+
+    use super::{discard_invalid_words, retry_transient_failures, verify_local_dump_file};
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_retry_transient_failures() {
+        let attempts = AtomicUsize::new(0);
+        let transient_then_success = || async {
+            if attempts.fetch_add(1, Ordering::Relaxed) == 0 {
+                Err(RVocError::DownloadWiktionaryDump {
+                    source: "injected network error".into(),
+                })
+            } else {
+                Ok(())
+            }
+        };
+
+        // a transient failure is retried
+        assert!(
+            retry_transient_failures(1, Duration::zero(), transient_then_success)
+                .await
+                .is_ok()
+        );
+        assert_eq!(attempts.load(Ordering::Relaxed), 2);
+
+        // but only up to the retry count
+        attempts.store(0, Ordering::Relaxed);
+        assert!(matches!(
+            retry_transient_failures(0, Duration::zero(), transient_then_success).await,
+            Err(RVocError::DownloadWiktionaryDump { .. })
+        ));
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+
+        // permanent failures are not retried
+        attempts.store(0, Ordering::Relaxed);
+        assert!(matches!(
+            retry_transient_failures(3, Duration::zero(), || async {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                Result::<(), _>::Err(RVocError::ParseWiktionaryDump {
+                    source: "injected parse error".into(),
+                })
+            })
+            .await,
+            Err(RVocError::ParseWiktionaryDump { .. })
+        ));
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_discard_invalid_words() {
+        let word = |word: &str| Word {
+            word: word.to_owned(),
+            language_english_name: "English".to_owned(),
+            word_type: "Noun".to_owned(),
+        };
+        let mut word_buffer = vec![
+            word("house"),
+            word(""),
+            word(" \t"),
+            word("abcde"),
+            word("abcdef"),
+            word("a b"),
+        ];
+
+        assert_eq!(discard_invalid_words(&mut word_buffer, 5), 3);
+        assert_eq!(
+            word_buffer
+                .iter()
+                .map(|word| word.word.as_str())
+                .collect::<Vec<_>>(),
+            ["house", "abcde", "a b"]
+        );
+    }
+
+    #[test]
+    fn test_verify_local_dump_file() {
+        assert!(matches!(
+            verify_local_dump_file(Path::new("does/not/exist.xml.bz2")),
+            Err(RVocError::WiktionaryDumpFileDoesNotExist { .. })
+        ));
+        assert!(matches!(
+            verify_local_dump_file(Path::new(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/Cargo.toml"
+            ))),
+            Err(RVocError::WiktionaryDumpFileExtension { .. })
+        ));
+        verify_local_dump_file(Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test-data/enwiktionary-fixture-pages-articles.xml.bz2"
+        )))
+        .unwrap();
+    }
+}
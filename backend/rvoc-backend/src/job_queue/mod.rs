@@ -15,7 +15,9 @@ use crate::{
     database::{model::ScheduledJob, RVocAsyncDatabaseConnectionPool},
     error::{RVocError, RVocResult},
     job_queue::jobs::{
-        delete_expired_sessions::delete_expired_sessions, update_witkionary::update_wiktionary,
+        delete_expired_sessions::delete_expired_sessions,
+        expire_inactive_accounts::expire_inactive_accounts, refresh_word_stats::refresh_word_stats,
+        update_witkionary::update_wiktionary,
     },
 };
 
@@ -114,11 +116,19 @@ async fn initialise_job_queue(
 }
 
 #[instrument(err, skip(database_connection_pool, configuration))]
-async fn poll_job_queue_and_execute(
+pub async fn poll_job_queue_and_execute(
     database_connection_pool: &RVocAsyncDatabaseConnectionPool,
     configuration: &Configuration,
 ) -> RVocResult<()> {
     if let Some(job) = reserve_job(database_connection_pool, configuration).await? {
+        if !configuration.should_run_job(job.name) {
+            warn!(
+                "Not running job {} because it is disabled in the current configuration",
+                job.name
+            );
+            return complete_job(job, database_connection_pool, configuration).await;
+        }
+
         debug!("Executing job {job:?}");
 
         match job.name {
@@ -128,6 +138,12 @@ async fn poll_job_queue_and_execute(
             JobName::DeleteExpiredSessions => {
                 delete_expired_sessions(database_connection_pool, configuration).await?
             }
+            JobName::RefreshWordStats => {
+                refresh_word_stats(database_connection_pool, configuration).await?
+            }
+            JobName::ExpireInactiveAccounts => {
+                expire_inactive_accounts(database_connection_pool, configuration).await?
+            }
         }
 
         complete_job(job, database_connection_pool, configuration).await
@@ -166,7 +182,8 @@ async fn reserve_job(
 
                     if let Some(queued_job) = queued_job {
                         // Convert the job name into JobName.
-                        // If it does not exist, then we delete the corresponding job.
+                        // The database only accepts known job names, but a newer version of the application
+                        // may know more jobs than this one. If it does not exist, then we delete the corresponding job.
                         let job_name = match JobName::from_str(&queued_job.name) {
                             Ok(job_name) => job_name,
                             Err(error) => {
@@ -273,10 +290,16 @@ async fn complete_job(
         })
 }
 
+/// The jobs that can be scheduled in the job queue.
+///
+/// The `job_queue` table only accepts these names, so adding a job requires a migration
+/// that adds its name to the `job_queue_name_check` constraint.
 #[derive(Debug, Eq, PartialEq, Clone, Copy, EnumString, Display, AsRefStr, EnumIter)]
 pub enum JobName {
     UpdateWiktionary,
     DeleteExpiredSessions,
+    RefreshWordStats,
+    ExpireInactiveAccounts,
 }
 
 #[derive(Debug)]
@@ -314,6 +337,12 @@ impl CompletedJob {
             JobName::DeleteExpiredSessions => self
                 .finish_time
                 .max(self.start_time + configuration.delete_expired_sessions_interval),
+            JobName::RefreshWordStats => self
+                .finish_time
+                .max(self.start_time + configuration.refresh_word_stats_interval),
+            JobName::ExpireInactiveAccounts => self
+                .finish_time
+                .max(self.start_time + configuration.expire_inactive_accounts_interval),
         }
     }
 
@@ -1,7 +1,7 @@
 use crate::error::RVocResult;
 use crate::{configuration::Configuration, error::RVocError};
 use cli::run_cli_command;
-use tracing::{info, instrument, Level};
+use tracing::{info, instrument, warn, Level};
 use tracing_subscriber::filter::FilterFn;
 use tracing_subscriber::Layer;
 
@@ -84,6 +84,14 @@ async fn main() -> RVocResult<()> {
     let configuration = Configuration::from_environment()?;
 
     setup_tracing_subscriber(&configuration)?;
+    configuration.validate()?;
+
+    if configuration.dev_mode {
+        warn!(
+            "RVOC_DEV_MODE is enabled, so if PASSWORD_PEPPER is unset, the pepper from {:?} is used. Never enable dev mode in production!",
+            configuration::DEV_PASSWORD_PEPPER_FILE
+        );
+    }
 
     run_cli_command(&configuration).await?;
 
@@ -0,0 +1,154 @@
+use secure_string::SecureBytes;
+use sha1::{Digest, Sha1};
+use tracing::{instrument, warn};
+
+use crate::{
+    configuration::Configuration,
+    error::{RVocError, RVocResult, UserError},
+};
+
+/// The number of hex digits of the password hash that are sent to the Have I Been Pwned API.
+const HASH_PREFIX_LENGTH: usize = 5;
+
+/// Checks passwords against the Have I Been Pwned database of breached passwords.
+///
+/// This uses the k-anonymity range API, so only the first five hex digits of the SHA-1 hash of the password leave the server.
+/// The HTTP client is shared between all checks, such that connections to the API are reused.
+#[derive(Debug, Clone)]
+pub struct CompromisedPasswordCheck {
+    client: reqwest::Client,
+}
+
+impl CompromisedPasswordCheck {
+    pub fn new(configuration: &Configuration) -> RVocResult<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(configuration.hibp_timeout.to_std().unwrap())
+            .build()
+            .map_err(|error| RVocError::BuildHibpClient {
+                source: Box::new(error),
+            })?;
+
+        Ok(Self { client })
+    }
+
+    /// Reject passwords that appear in the Have I Been Pwned database of breached passwords.
+    ///
+    /// If the API cannot be reached, the password is accepted and a warning is logged,
+    /// since an unavailable third-party service should not prevent signups.
+    #[instrument(err, skip(self, password, configuration))]
+    pub async fn verify_password_not_compromised(
+        &self,
+        password: &SecureBytes,
+        configuration: &Configuration,
+    ) -> RVocResult<()> {
+        if self.is_password_compromised(password, configuration).await == Some(true) {
+            Err(UserError::CompromisedPassword)?
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check if the password appears in the Have I Been Pwned database of breached passwords.
+    /// Returns `None` if the check is disabled, or if the API cannot be reached.
+    pub async fn is_password_compromised(
+        &self,
+        password: &SecureBytes,
+        configuration: &Configuration,
+    ) -> Option<bool> {
+        if !configuration.hibp_check_enabled {
+            return None;
+        }
+
+        let password_hash: String = Sha1::digest(password.unsecure())
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect();
+        let (prefix, suffix) = password_hash.split_at(HASH_PREFIX_LENGTH);
+
+        match self.request_hash_range(prefix, configuration).await {
+            Ok(response) => Some(range_response_contains_suffix(&response, suffix)),
+            Err(error) => {
+                warn!("Could not check if a password is compromised: {error}");
+                None
+            }
+        }
+    }
+
+    async fn request_hash_range(
+        &self,
+        prefix: &str,
+        configuration: &Configuration,
+    ) -> Result<String, reqwest::Error> {
+        self.client
+            .get(format!("{}{prefix}", configuration.hibp_api_url))
+            // Padding makes all responses have a similar size, so the prefix cannot be inferred from the response size.
+            .header("Add-Padding", "true")
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await
+    }
+}
+
+/// The response contains one `SUFFIX:COUNT` line per breached hash with the requested prefix.
+/// Padding lines have a count of zero.
+fn range_response_contains_suffix(response: &str, suffix: &str) -> bool {
+    response.lines().any(|line| {
+        line.trim()
+            .split_once(':')
+            .is_some_and(|(line_suffix, count)| {
+                line_suffix.eq_ignore_ascii_case(suffix)
+                    && count.parse::<u64>().is_ok_and(|count| count > 0)
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use secure_string::SecureBytes;
+
+    use crate::configuration::Configuration;
+
+    use super::{range_response_contains_suffix, CompromisedPasswordCheck};
+
+    // The SHA-1 of "password" is 5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8.
+    const PASSWORD_SUFFIX: &str = "1E4C9B93F3F0682250B6CF8331B7EE68FD8";
+
+    #[test]
+    fn test_breached_password() {
+        let response = "003D68EB55068C33ACE09247EE4C639306B:3\r\n\
+                        1E4C9B93F3F0682250B6CF8331B7EE68FD8:10434004\r\n\
+                        01330C689E5D64F660D6947A93AD634EF8F:0";
+        assert!(range_response_contains_suffix(response, PASSWORD_SUFFIX));
+        assert!(range_response_contains_suffix(
+            &response.to_lowercase(),
+            PASSWORD_SUFFIX
+        ));
+    }
+
+    #[test]
+    fn test_clean_password() {
+        let response = "003D68EB55068C33ACE09247EE4C639306B:3\r\n\
+                        01330C689E5D64F660D6947A93AD634EF8F:0";
+        assert!(!range_response_contains_suffix(response, PASSWORD_SUFFIX));
+
+        // padding entries do not count
+        let response = "1E4C9B93F3F0682250B6CF8331B7EE68FD8:0";
+        assert!(!range_response_contains_suffix(response, PASSWORD_SUFFIX));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_unreachable_service_fails_open() {
+        let mut configuration = Configuration::test_configuration();
+        configuration.hibp_check_enabled = true;
+        // Nothing listens on port 1.
+        configuration.hibp_api_url = "http://127.0.0.1:1/range/".to_owned();
+
+        CompromisedPasswordCheck::new(&configuration)
+            .unwrap()
+            .verify_password_not_compromised(&SecureBytes::from("password"), &configuration)
+            .await
+            .unwrap();
+    }
+}
@@ -2,6 +2,7 @@ use diesel::prelude::Insertable;
 
 use self::{password_hash::PasswordHash, username::Username};
 
+pub mod compromised_password;
 pub mod password_hash;
 pub mod username;
 
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use argon2::Argon2;
 use argon2::PasswordHasher;
 use password_hash::PasswordVerifier;
@@ -53,6 +55,41 @@ impl PasswordHash {
         Ok(Self { argon_hash })
     }
 
+    /// Like [`Self::new`], but hashes on the blocking thread pool.
+    /// Hashing is slow by design, so doing it on an async worker thread would stall other requests.
+    pub async fn new_async(
+        plaintext_password: SecureBytes,
+        configuration: Arc<Configuration>,
+    ) -> RVocResult<Self> {
+        tokio::task::spawn_blocking(move || Self::new(plaintext_password, configuration))
+            .await
+            .map_err(|error| RVocError::PasswordHashingTask {
+                source: Box::new(error),
+            })?
+    }
+
+    /// Like [`Self::verify`], but verifies on the blocking thread pool.
+    /// Verifying is as slow as hashing, so doing it on an async worker thread would stall other requests.
+    pub async fn verify_async(
+        &mut self,
+        plaintext_password: SecureBytes,
+        configuration: Arc<Configuration>,
+    ) -> RVocResult<VerifyPasswordResult> {
+        let mut password_hash = self.clone();
+        let (password_hash, verify_result) = tokio::task::spawn_blocking(move || {
+            password_hash
+                .verify(plaintext_password, configuration)
+                .map(|verify_result| (password_hash, verify_result))
+        })
+        .await
+        .map_err(|error| RVocError::PasswordHashingTask {
+            source: Box::new(error),
+        })??;
+
+        *self = password_hash;
+        Ok(verify_result)
+    }
+
     pub fn verify(
         &mut self,
         plaintext_password: SecureBytes,
@@ -74,36 +111,49 @@ impl PasswordHash {
                     source: Box::new(error),
                 }
             })?;
-        let argon2 = Self::build_argon2_from_parameters(
-            argon2::Params::try_from(&parsed_hash).map_err(|error| {
-                RVocError::PasswordArgon2IdVerify {
-                    source: Box::new(error),
+        let parameters = argon2::Params::try_from(&parsed_hash).map_err(|error| {
+            RVocError::PasswordArgon2IdVerify {
+                source: Box::new(error),
+            }
+        })?;
+
+        // Try the current pepper first, and then the previous ones.
+        let password_peppers = std::iter::once(&configuration.password_pepper)
+            .chain(&configuration.previous_password_peppers);
+
+        for (pepper_index, password_pepper) in password_peppers.enumerate() {
+            let argon2 =
+                Self::build_argon2_from_parameters(parameters.clone(), password_pepper.unsecure())?;
+
+            match argon2.verify_password(plaintext_password.unsecure(), &parsed_hash) {
+                Ok(()) => {
+                    let is_previous_pepper = pepper_index > 0;
+                    let modified = if is_previous_pepper
+                        || self.did_parameters_change(&parsed_hash, configuration)?
+                    {
+                        *self = Self::new(plaintext_password, configuration)?;
+                        true
+                    } else {
+                        false
+                    };
+                    return Ok(VerifyPasswordResult {
+                        matches: true,
+                        modified,
+                    });
+                }
+                Err(argon2::password_hash::Error::Password) => {}
+                Err(error) => {
+                    return Err(RVocError::PasswordArgon2IdVerify {
+                        source: Box::new(error),
+                    })
                 }
-            })?,
-            configuration,
-        )?;
-
-        match argon2.verify_password(plaintext_password.unsecure(), &parsed_hash) {
-            Ok(()) => {
-                let modified = if self.did_parameters_change(&parsed_hash, configuration)? {
-                    *self = Self::new(plaintext_password, configuration)?;
-                    true
-                } else {
-                    false
-                };
-                Ok(VerifyPasswordResult {
-                    matches: true,
-                    modified,
-                })
             }
-            Err(argon2::password_hash::Error::Password) => Ok(VerifyPasswordResult {
-                matches: false,
-                modified: false,
-            }),
-            Err(error) => Err(RVocError::PasswordArgon2IdVerify {
-                source: Box::new(error),
-            }),
         }
+
+        Ok(VerifyPasswordResult {
+            matches: false,
+            modified: false,
+        })
     }
 
     /// Check if the password hashing parameters are different from the ones used for this hash.
@@ -132,10 +182,10 @@ impl PasswordHash {
 
     fn build_argon2_from_parameters(
         parameters: argon2::Params,
-        configuration: &Configuration,
+        password_pepper: &[u8],
     ) -> RVocResult<Argon2<'_>> {
         Argon2::new_with_secret(
-            configuration.password_pepper.unsecure(),
+            password_pepper,
             HASH_ALGORITHM,
             HASH_ALGORITHM_VERSION,
             // the correctness of the parameters was checked when creating the configuration
@@ -147,7 +197,10 @@ impl PasswordHash {
     }
 
     fn build_argon2(configuration: &Configuration) -> RVocResult<Argon2<'_>> {
-        Self::build_argon2_from_parameters(configuration.build_argon2_parameters()?, configuration)
+        Self::build_argon2_from_parameters(
+            configuration.build_argon2_parameters()?,
+            configuration.password_pepper.unsecure(),
+        )
     }
 }
 
@@ -187,10 +240,18 @@ impl From<String> for PasswordHash {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
     use secure_string::SecureBytes;
 
     use super::{PasswordHash, VerifyPasswordResult, HASH_ALGORITHM, HASH_ALGORITHM_VERSION};
-    use crate::configuration::Configuration;
+    use crate::{
+        configuration::Configuration,
+        error::{RVocError, UserError},
+    };
 
     #[test]
     fn test_password_check() {
@@ -236,6 +297,85 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_async_hashing_does_not_block_the_runtime() {
+        let configuration = Arc::new(Configuration::test_configuration());
+        let password = SecureBytes::from("mypassword");
+
+        // This task can only make progress while the hashing does not occupy the single runtime thread.
+        let progress = Arc::new(AtomicUsize::new(0));
+        let ticker = tokio::spawn({
+            let progress = progress.clone();
+            async move {
+                loop {
+                    progress.fetch_add(1, Ordering::Relaxed);
+                    tokio::task::yield_now().await;
+                }
+            }
+        });
+
+        let mut password_hash = PasswordHash::new_async(password.clone(), configuration.clone())
+            .await
+            .unwrap();
+        let progress_while_hashing = progress.load(Ordering::Relaxed);
+        let verify_password_result = password_hash
+            .verify_async(password, configuration)
+            .await
+            .unwrap();
+        let progress_while_verifying = progress.load(Ordering::Relaxed) - progress_while_hashing;
+        ticker.abort();
+
+        assert_eq!(
+            verify_password_result,
+            VerifyPasswordResult {
+                matches: true,
+                modified: false,
+            }
+        );
+        assert!(progress_while_hashing > 0);
+        assert!(progress_while_verifying > 0);
+    }
+
+    #[test]
+    fn test_password_length_boundaries() {
+        let configuration = Configuration::test_configuration();
+        let minimum = configuration.minimum_password_length;
+        let maximum = configuration.maximum_password_length;
+
+        for length in [0, minimum - 1, maximum + 1, 10 * maximum] {
+            let password = SecureBytes::from("a".repeat(length));
+            assert!(
+                matches!(
+                    PasswordHash::new(password.clone(), &configuration),
+                    Err(RVocError::UserError(UserError::PasswordLength { .. }))
+                ),
+                "password of length {length} was hashed"
+            );
+
+            // verifying does not hash passwords of disallowed lengths either
+            let mut password_hash =
+                PasswordHash::new(SecureBytes::from("a".repeat(minimum)), &configuration).unwrap();
+            assert!(
+                matches!(
+                    password_hash.verify(password, &configuration),
+                    Err(RVocError::UserError(UserError::PasswordLength { .. }))
+                ),
+                "password of length {length} was verified"
+            );
+        }
+
+        for length in [minimum, maximum] {
+            let password = SecureBytes::from("a".repeat(length));
+            let mut password_hash = PasswordHash::new(password.clone(), &configuration).unwrap();
+            assert!(
+                password_hash
+                    .verify(password, &configuration)
+                    .unwrap()
+                    .matches
+            );
+        }
+    }
+
     #[test]
     fn test_empty_password_hash() {
         let configuration = Configuration::test_configuration();
@@ -278,4 +418,52 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_previous_password_pepper() {
+        let mut old_configuration = Configuration::test_configuration();
+        old_configuration.password_pepper = "oldpepper123".into();
+        let mut new_configuration = Configuration::test_configuration();
+        new_configuration.password_pepper = "newpepper123".into();
+
+        let password = SecureBytes::from("mypassword");
+        let old_password_hash = PasswordHash::new(password.clone(), &old_configuration).unwrap();
+
+        // without the old pepper, the password does not match
+        let mut password_hash = old_password_hash.clone();
+        assert_eq!(
+            password_hash
+                .verify(password.clone(), &new_configuration)
+                .unwrap(),
+            VerifyPasswordResult {
+                matches: false,
+                modified: false,
+            }
+        );
+
+        // with the old pepper, the password matches and is rehashed
+        new_configuration.previous_password_peppers = vec!["oldpepper123".into()];
+        let mut password_hash = old_password_hash;
+        assert_eq!(
+            password_hash
+                .verify(password.clone(), &new_configuration)
+                .unwrap(),
+            VerifyPasswordResult {
+                matches: true,
+                modified: true,
+            }
+        );
+
+        // the rehashed password matches without the old pepper
+        new_configuration.previous_password_peppers = Vec::new();
+        assert_eq!(
+            password_hash
+                .verify(password.clone(), &new_configuration)
+                .unwrap(),
+            VerifyPasswordResult {
+                matches: true,
+                modified: false,
+            }
+        );
+    }
 }
@@ -0,0 +1,166 @@
+use api_commands::{ApiTokenInfo, CreateApiToken, CreatedApiToken};
+use axum::{extract::Path, http::StatusCode, Extension};
+use rand::RngCore;
+use tracing::instrument;
+
+use crate::{
+    database::api_tokens::{hash_api_token, to_hex},
+    error::{RVocError, RVocResult, UserError},
+};
+
+use super::{
+    authentication::LoggedInUser, json::Json, WebConfiguration, WebDatabaseConnectionPool,
+};
+
+/// The number of random bytes in an API token.
+const API_TOKEN_LENGTH: usize = 32;
+
+/// Create a new API token for the logged in user.
+/// The token is returned only in this response, the database stores only its hash.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn create_api_token(
+    Extension(username): Extension<LoggedInUser>,
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Json(create_api_token): Json<CreateApiToken>,
+) -> RVocResult<(StatusCode, Json<CreatedApiToken>)> {
+    let CreateApiToken { label, expiry } = create_api_token;
+    configuration.verify_api_token_label_length(&label)?;
+
+    let mut token = [0u8; API_TOKEN_LENGTH];
+    rand::rngs::OsRng.fill_bytes(&mut token);
+    let token = to_hex(&token);
+    let token_hash = hash_api_token(&token);
+
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::api_tokens;
+                    use diesel::ExpressionMethods;
+                    use diesel_async::RunQueryDsl;
+
+                    let id = diesel::insert_into(api_tokens::table)
+                        .values((
+                            api_tokens::username.eq(username.as_ref()),
+                            api_tokens::token_hash.eq(&token_hash),
+                            api_tokens::label.eq(&label),
+                            api_tokens::expiry.eq(expiry),
+                        ))
+                        .returning(api_tokens::id)
+                        .get_result(database_connection)
+                        .await?;
+
+                    Ok((
+                        StatusCode::CREATED,
+                        Json(CreatedApiToken {
+                            id,
+                            token: token.clone(),
+                        }),
+                    ))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::CreateApiToken {
+                source: Box::new(error),
+            },
+        })
+}
+
+/// List the API tokens of the logged in user, including expired ones.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn list_api_tokens(
+    Extension(username): Extension<LoggedInUser>,
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+) -> RVocResult<Json<Vec<ApiTokenInfo>>> {
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::api_tokens;
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let api_tokens = api_tokens::table
+                        .select((
+                            api_tokens::id,
+                            api_tokens::label,
+                            api_tokens::created_at,
+                            api_tokens::expiry,
+                        ))
+                        .filter(api_tokens::username.eq(username.as_ref()))
+                        .order_by(api_tokens::id.asc())
+                        .load(database_connection)
+                        .await?;
+
+                    Ok(Json(
+                        api_tokens
+                            .into_iter()
+                            .map(|(id, label, created_at, expiry)| ApiTokenInfo {
+                                id,
+                                label,
+                                created_at,
+                                expiry,
+                            })
+                            .collect(),
+                    ))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::ListApiTokens {
+                source: Box::new(error),
+            },
+        })
+}
+
+/// Revoke an API token of the logged in user.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn delete_api_token(
+    Extension(username): Extension<LoggedInUser>,
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Path(id): Path<i32>,
+) -> RVocResult<StatusCode> {
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::api_tokens;
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    // Tokens of other users are treated as not existing.
+                    let affected_rows = diesel::delete(api_tokens::table)
+                        .filter(api_tokens::id.eq(id))
+                        .filter(api_tokens::username.eq(username.as_ref()))
+                        .execute(database_connection)
+                        .await?;
+
+                    if affected_rows == 0 {
+                        Err(UserError::ApiTokenDoesNotExist { id }.into())
+                    } else {
+                        Ok(StatusCode::NO_CONTENT)
+                    }
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::DeleteApiToken {
+                source: Box::new(error),
+            },
+        })
+}
@@ -1,22 +1,55 @@
+use std::sync::Arc;
+
 use api_commands::Login;
 use axum::{
-    http::{Request, StatusCode},
+    extract::State,
+    http::{header, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
-    Extension, Json,
+    Extension,
 };
+use chrono::{DateTime, Utc};
 use diesel::QueryDsl;
 use tracing::{info, instrument};
 use typed_session_axum::{SessionHandle, WritableSession};
 
 use crate::{
+    configuration::Configuration,
+    database::{api_tokens::find_api_token_user, RVocAsyncDatabaseConnectionPool},
     error::{RVocError, RVocResult, UserError},
     model::user::{password_hash::PasswordHash, username::Username},
 };
 
-use super::{session::RVocSessionData, WebConfiguration, WebDatabaseConnectionPool};
+use super::{json::Json, session::RVocSessionData, WebConfiguration, WebDatabaseConnectionPool};
 
+/// Require that the request is made by a logged in user.
+/// Clients that cannot use the session cookie may instead authenticate with an api token
+/// in an `Authorization: Bearer <token>` header.
 pub async fn ensure_logged_in<B>(mut request: Request<B>, next: Next<B>) -> Response {
+    if let Some(authorization) = request.headers().get(header::AUTHORIZATION) {
+        let Some(token) = authorization
+            .to_str()
+            .ok()
+            .and_then(|authorization| authorization.strip_prefix("Bearer "))
+            .map(ToOwned::to_owned)
+        else {
+            return StatusCode::UNAUTHORIZED.into_response();
+        };
+        let database_connection_pool: &RVocAsyncDatabaseConnectionPool =
+            request.extensions().get().unwrap();
+        let configuration: &Arc<Configuration> = request.extensions().get().unwrap();
+
+        match find_api_token_user(&token, database_connection_pool, configuration).await {
+            Ok(Some(username)) => {
+                request.extensions_mut().insert(LoggedInUser(username));
+                request.extensions_mut().insert(ApiTokenAuthentication);
+                return next.run(request).await;
+            }
+            Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+            Err(error) => return error.into_response(),
+        }
+    }
+
     let session: &SessionHandle<RVocSessionData> = request.extensions().get().unwrap();
     let session = session.read().await;
     let session_data = session.data();
@@ -33,6 +66,57 @@ pub async fn ensure_logged_in<B>(mut request: Request<B>, next: Next<B>) -> Resp
     next.run(request).await
 }
 
+/// Require that the request is authenticated with the session cookie instead of an api token.
+/// This protects the routes that manage api tokens or delete the account, such that a leaked token
+/// cannot be used to create further tokens or to lock the user out.
+///
+/// Must be layered inside of [`ensure_logged_in`].
+pub async fn ensure_session_authentication<B>(request: Request<B>, next: Next<B>) -> Response {
+    if request
+        .extensions()
+        .get::<ApiTokenAuthentication>()
+        .is_some()
+    {
+        return UserError::SessionAuthenticationRequired.into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Reject state-changing requests whose `Origin` (or `Referer`) header is not in the allowlist.
+/// This is a defense against cross-site request forgery in addition to the cookie settings.
+/// Requests without these headers are not made by a browser on behalf of another site, so they are let through.
+pub async fn ensure_allowed_origin<B>(
+    State(configuration): State<Arc<Configuration>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    if configuration.api_allowed_origins.is_empty() || request.method().is_safe() {
+        return next.run(request).await;
+    }
+
+    let headers = request.headers();
+    let origin = if let Some(origin) = headers.get(header::ORIGIN) {
+        Some(String::from_utf8_lossy(origin.as_bytes()).into_owned())
+    } else {
+        headers.get(header::REFERER).map(|referer| {
+            let referer = String::from_utf8_lossy(referer.as_bytes());
+            // The origin of an url is everything before the first slash after the scheme.
+            match referer.match_indices('/').nth(2) {
+                Some((index, _)) => referer[..index].to_owned(),
+                None => referer.into_owned(),
+            }
+        })
+    };
+
+    match origin {
+        Some(origin) if !configuration.api_allowed_origins.contains(&origin) => {
+            UserError::OriginNotAllowed { origin }.into_response()
+        }
+        _ => next.run(request).await,
+    }
+}
+
 #[instrument(err, skip(database_connection_pool, configuration))]
 pub async fn login(
     Extension(database_connection_pool): WebDatabaseConnectionPool,
@@ -51,6 +135,7 @@ pub async fn login(
             |database_connection| {
                 Box::pin(async {
                     use crate::database::schema::users;
+                    use diesel::dsl::now;
                     use diesel::ExpressionMethods;
                     use diesel::OptionalExtension;
                     use diesel_async::RunQueryDsl;
@@ -58,15 +143,16 @@ pub async fn login(
                     let configuration = configuration.clone();
 
                     // get password hash
-                    let password_hash: String = if let Some(password_hash) = users::table
-                        .select(users::password_hash)
-                        .filter(users::name.eq(username.as_ref()))
-                        .first(database_connection)
-                        .await
-                        .optional()?
+                    let (password_hash, expired_at) = if let Some((password_hash, expired_at)) =
+                        users::table
+                            .select((users::password_hash, users::expired_at))
+                            .filter(users::name.eq(username.as_ref()))
+                            .first::<(Option<String>, Option<DateTime<Utc>>)>(database_connection)
+                            .await
+                            .optional()?
                     {
                         if let Some(password_hash) = password_hash {
-                            password_hash
+                            (password_hash, expired_at)
                         } else {
                             // Here the optional() returned a row, but with a null password hash.
                             info!("User has no password: {:?}", username);
@@ -80,14 +166,21 @@ pub async fn login(
 
                     // verify password hash
                     let mut password_hash = PasswordHash::from(password_hash);
-                    let verify_result =
-                        password_hash.verify(password.clone(), configuration)?;
+                    let verify_result = password_hash
+                        .verify_async(password.clone(), configuration)
+                        .await?;
 
                     if !verify_result.matches {
                         info!("Wrong password for user: {:?}", username);
                         return Err(UserError::InvalidUsernamePassword.into());
                     }
 
+                    // Only reveal that the account is expired to someone who knows the password.
+                    if let Some(expired_at) = expired_at {
+                        info!("Account of user {:?} expired at {expired_at}", username);
+                        return Err(UserError::AccountExpired.into());
+                    }
+
                     // update password hash if modified
                     if verify_result.modified {
                         let affected_rows = diesel::update(users::table)
@@ -103,6 +196,13 @@ pub async fn login(
                         }
                     }
 
+                    // record the login
+                    diesel::update(users::table)
+                        .filter(users::name.eq(username.as_ref()))
+                        .set(users::last_login_at.eq(now))
+                        .execute(database_connection)
+                        .await?;
+
                     Ok(())
                 })
             },
@@ -143,3 +243,7 @@ impl AsRef<str> for LoggedInUser {
         self.0.as_ref()
     }
 }
+
+/// If this extension is found, it means that the request was authenticated with an api token instead of the session cookie.
+#[derive(Debug, Clone, Copy)]
+pub struct ApiTokenAuthentication;
@@ -0,0 +1,57 @@
+use api_commands::Capabilities;
+use axum::Extension;
+
+use crate::configuration::Configuration;
+
+use super::{json::Json, WebConfiguration};
+
+/// Return the optional features and the limits of the server, so that clients can adapt their UI,
+/// e.g. by validating input before submitting it.
+pub async fn server_capabilities(Extension(configuration): WebConfiguration) -> Json<Capabilities> {
+    Json(capabilities(&configuration))
+}
+
+/// Derive the capabilities from the configuration.
+/// Only add settings here that are safe to publish, since this is available without logging in.
+fn capabilities(configuration: &Configuration) -> Capabilities {
+    Capabilities {
+        compromised_password_check: configuration.hibp_check_enabled,
+        account_inactivity_expiry: configuration.account_inactivity_expiry.is_some(),
+        minimum_username_length: configuration.minimum_username_length,
+        maximum_username_length: configuration.maximum_username_length,
+        minimum_password_length: configuration.minimum_password_length,
+        maximum_password_length: configuration.maximum_password_length,
+        maximum_api_token_label_length: configuration.maximum_api_token_label_length,
+        maximum_word_note_length: configuration.maximum_word_note_length,
+        maximum_word_report_reason_length: configuration.maximum_word_report_reason_length,
+        maximum_word_batch_size: configuration.maximum_word_batch_size,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::Configuration;
+
+    use super::capabilities;
+
+    #[test]
+    fn test_capabilities_follow_configuration() {
+        let mut configuration = Configuration::test_configuration();
+        configuration.hibp_check_enabled = false;
+        configuration.account_inactivity_expiry = None;
+        let capabilities_before = capabilities(&configuration);
+        assert!(!capabilities_before.compromised_password_check);
+        assert!(!capabilities_before.account_inactivity_expiry);
+
+        configuration.hibp_check_enabled = true;
+        configuration.account_inactivity_expiry = Some(chrono::Duration::try_days(365).unwrap());
+        configuration.maximum_word_batch_size += 1;
+        let capabilities_after = capabilities(&configuration);
+        assert!(capabilities_after.compromised_password_check);
+        assert!(capabilities_after.account_inactivity_expiry);
+        assert_eq!(
+            capabilities_after.maximum_word_batch_size,
+            capabilities_before.maximum_word_batch_size + 1
+        );
+    }
+}
@@ -0,0 +1,10 @@
+use axum::{extract::Path, http::StatusCode};
+
+/// Respond only after the given number of seconds.
+/// This simulates a slow handler, to test that the graceful shutdown is bounded by the shutdown timeout.
+///
+/// This route only exists in integration test mode.
+pub async fn sleep(Path(seconds): Path<u64>) -> StatusCode {
+    tokio::time::sleep(std::time::Duration::from_secs(seconds)).await;
+    StatusCode::NO_CONTENT
+}
@@ -0,0 +1,49 @@
+use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest},
+    http::Request,
+    response::{IntoResponse, Response},
+};
+
+use crate::error::UserError;
+
+/// A replacement for [`axum::Json`] that rejects request bodies which cannot be deserialised with `400 Bad Request`.
+///
+/// Axum answers JSON with a wrong structure with `422 Unprocessable Entity`, and its message does not fit our other errors.
+/// Other rejections are passed through unchanged, so a missing or wrong content type is still answered with `415 Unsupported Media Type`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+#[async_trait]
+impl<T, S, B> FromRequest<S, B> for Json<T>
+where
+    axum::Json<T>: FromRequest<S, B, Rejection = JsonRejection>,
+    S: Send + Sync,
+    B: Send + 'static,
+{
+    type Rejection = Response;
+
+    async fn from_request(request: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::Json::<T>::from_request(request, state).await {
+            Ok(axum::Json(value)) => Ok(Self(value)),
+            Err(JsonRejection::JsonDataError(error)) => Err(UserError::MalformedJson {
+                message: error.body_text(),
+            }
+            .into_response()),
+            Err(JsonRejection::JsonSyntaxError(error)) => Err(UserError::MalformedJson {
+                message: error.body_text(),
+            }
+            .into_response()),
+            Err(rejection) => Err(rejection.into_response()),
+        }
+    }
+}
+
+impl<T> IntoResponse for Json<T>
+where
+    axum::Json<T>: IntoResponse,
+{
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
@@ -0,0 +1,114 @@
+use api_commands::{LanguageWordCount, WordTypeCount};
+use axum::{extract::Path, Extension};
+use tracing::instrument;
+
+use crate::error::{RVocError, RVocResult, UserError};
+
+use super::{json::Json, WebConfiguration, WebDatabaseConnectionPool};
+
+/// List all languages, together with their number of words.
+///
+/// The word counts are read from a materialized view, so they may lag behind
+/// by up to [`Configuration::refresh_word_stats_interval`](crate::configuration::Configuration::refresh_word_stats_interval).
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn list_languages(
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+) -> RVocResult<Json<Vec<LanguageWordCount>>> {
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::languages;
+                    use crate::database::views::language_word_counts;
+                    use diesel::NullableExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let language_word_counts: Vec<(String, Option<i64>)> = languages::table
+                        .left_join(language_word_counts::table)
+                        .select((
+                            languages::english_name,
+                            language_word_counts::word_count.nullable(),
+                        ))
+                        .order_by(languages::english_name)
+                        .load(database_connection)
+                        .await?;
+
+                    Ok(Json(
+                        language_word_counts
+                            .into_iter()
+                            .map(|(language, word_count)| LanguageWordCount {
+                                language,
+                                word_count: word_count.unwrap_or(0),
+                            })
+                            .collect(),
+                    ))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| RVocError::ListLanguages {
+            source: Box::new(error),
+        })
+}
+
+/// List the word types that occur in the given language, together with their number of words.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn list_language_word_types(
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Path(language): Path<String>,
+) -> RVocResult<Json<Vec<WordTypeCount>>> {
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::{languages, word_types, words};
+                    use diesel::dsl::count_star;
+                    use diesel::ExpressionMethods;
+                    use diesel::OptionalExtension;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let language_id: i32 = languages::table
+                        .select(languages::id)
+                        .filter(languages::english_name.eq(&language))
+                        .first(database_connection)
+                        .await
+                        .optional()?
+                        .ok_or_else(|| UserError::LanguageDoesNotExist {
+                            language: language.clone(),
+                        })?;
+
+                    let word_type_counts: Vec<(String, i64)> = words::table
+                        .inner_join(word_types::table)
+                        .filter(words::language.eq(language_id))
+                        .group_by(word_types::english_name)
+                        .select((word_types::english_name, count_star()))
+                        .order_by(word_types::english_name)
+                        .load(database_connection)
+                        .await?;
+
+                    Ok(Json(
+                        word_type_counts
+                            .into_iter()
+                            .map(|(word_type, word_count)| WordTypeCount {
+                                word_type,
+                                word_count,
+                            })
+                            .collect(),
+                    ))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::ListLanguageWordTypes {
+                source: Box::new(error),
+            },
+        })
+}
@@ -1,96 +1,358 @@
 use std::{convert::Infallible, fmt::Display, sync::Arc};
 
+use api_commands::ErrorResponse;
 use axum::{
     error_handling::HandleErrorLayer,
-    http::StatusCode,
-    middleware,
-    response::IntoResponse,
-    routing::{delete, post},
-    Extension, Router,
+    extract::State,
+    http::{header, HeaderValue, Request, StatusCode, Uri},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Extension, Router, ServiceExt,
 };
-use tower::ServiceBuilder;
-use tracing::{debug, error, info, instrument};
+use tokio::{sync::Notify, time::Instant};
+use tower::{Layer, ServiceBuilder};
+use tracing::{debug, error, info, instrument, warn};
+use typed_session::SessionRenewalStrategy;
 use typed_session_axum::{SessionLayer, SessionLayerError};
 
 use crate::{
     configuration::Configuration,
     database::RVocAsyncDatabaseConnectionPool,
     error::{RVocError, RVocResult, UserError},
+    model::user::compromised_password::CompromisedPasswordCheck,
     web::{
-        authentication::{ensure_logged_in, login, logout},
+        api_token::{create_api_token, delete_api_token, list_api_tokens},
+        authentication::{
+            ensure_allowed_origin, ensure_logged_in, ensure_session_authentication, login, logout,
+        },
+        capabilities::server_capabilities,
+        json::Json,
+        language::{list_language_word_types, list_languages},
+        rate_limit::RateLimiter,
         session::{RVocSessionData, RVocSessionStoreConnector},
-        user::{create_account, delete_account},
+        time::server_time,
+        user::{account_security, create_account, delete_account, rate_password, whoami},
+        word::{
+            check_words_exist, delete_word_note, detect_language, find_lemmas, get_word_note,
+            list_common_words, list_translations, random_word, report_word, set_word_note,
+        },
     },
 };
 
+mod api_token;
 mod authentication;
+mod capabilities;
+mod integration_test;
+mod json;
+mod language;
+mod rate_limit;
 mod session;
+mod time;
 mod user;
+mod word;
 
+/// Run the web API until a shutdown signal is received.
+///
+/// Returns the deadline until which the rest of the application should have shut down as well.
 #[instrument(err, skip(database_connection_pool, configuration))]
 pub async fn run_web_api(
     database_connection_pool: RVocAsyncDatabaseConnectionPool,
     configuration: &Configuration,
-) -> RVocResult<()> {
+) -> RVocResult<Instant> {
     info!("Starting web API");
 
-    async fn handle_session_layer_error<
-        SessionStoreConnectorError: Display,
-        InnerError: Display,
-    >(
-        error: SessionLayerError<SessionStoreConnectorError, InnerError>,
-    ) -> StatusCode {
-        error!("Session layer error: {error}");
-        StatusCode::INTERNAL_SERVER_ERROR
-    }
-
     let configuration = Arc::new(configuration.clone());
+    let security_headers = Arc::new(SecurityHeaders::new(&configuration)?);
+    let compromised_password_check = CompromisedPasswordCheck::new(&configuration)?;
+    let password_rating_rate_limiter = Arc::new(RateLimiter::new(
+        configuration.maximum_password_rating_breach_checks_per_minute,
+    ));
+
+    if !configuration.cookie_secure {
+        warn!("The session cookie is sent without the Secure attribute, this must only be used for local development");
+    }
 
     let router = Router::new()
         .route("/accounts/delete", delete(delete_account))
+        .route(
+            "/accounts/tokens",
+            get(list_api_tokens).post(create_api_token),
+        )
+        .route("/accounts/tokens/:id", delete(delete_api_token))
+        .layer(middleware::from_fn(ensure_session_authentication))
         .route("/accounts/logout", post(logout))
+        .route("/accounts/whoami", get(whoami))
+        .route("/accounts/security", get(account_security))
+        .route(
+            "/words/note",
+            get(get_word_note)
+                .put(set_word_note)
+                .delete(delete_word_note),
+        )
+        .route("/words/report", post(report_word))
         .layer(middleware::from_fn(ensure_logged_in))
         .route("/accounts/login", post(login))
         .route("/accounts/create", post(create_account))
+        .route("/accounts/password-strength", post(rate_password))
+        .route("/languages", get(list_languages))
+        .route(
+            "/languages/:language/word-types",
+            get(list_language_word_types),
+        )
+        .route("/capabilities", get(server_capabilities))
+        .route("/time", get(server_time))
+        .route("/words/common", get(list_common_words))
+        .route("/words/detect-language", get(detect_language))
+        .route("/words/exists", post(check_words_exist))
+        .route("/words/lemma", get(find_lemmas))
+        .route("/words/random", get(random_word))
+        .route(
+            "/words/:language/:word/translations",
+            get(list_translations),
+        );
+    let router = if configuration.integration_test_mode {
+        router.route("/test/sleep/:seconds", get(integration_test::sleep))
+    } else {
+        router
+    };
+    let router = router
+        .fallback(route_does_not_exist)
         .layer(
             ServiceBuilder::new()
                 .layer(HandleErrorLayer::new(
                     handle_session_layer_error::<RVocError, Infallible>,
                 ))
-                .layer(SessionLayer::<RVocSessionData, RVocSessionStoreConnector>::new()),
+                .layer(
+                    SessionLayer::<RVocSessionData, RVocSessionStoreConnector>::new()
+                        .with_secure(configuration.cookie_secure)
+                        .with_session_renewal_strategy(SessionRenewalStrategy::AutomaticRenewal {
+                            time_to_live: configuration.session_time_to_live,
+                            // Sessions are renewed only once their remaining time to live
+                            // has dropped by at least the minimum renewal interval.
+                            maximum_remaining_time_to_live_for_renewal: configuration
+                                .session_time_to_live
+                                - configuration.session_minimum_renewal_interval,
+                        }),
+                ),
         )
         .layer(Extension(RVocSessionStoreConnector::new(
             database_connection_pool.clone(),
             configuration.clone(),
         )))
         .layer(Extension(database_connection_pool))
-        .layer(Extension(configuration.clone()));
+        .layer(Extension(compromised_password_check))
+        .layer(Extension(password_rating_rate_limiter))
+        .layer(Extension(configuration.clone()))
+        .layer(middleware::from_fn_with_state(
+            configuration.clone(),
+            ensure_allowed_origin,
+        ))
+        .layer(middleware::from_fn_with_state(
+            security_headers,
+            set_security_headers,
+        ));
+    // Path rewriting needs to happen before routing, so it cannot be a layer of the router.
+    let service =
+        middleware::from_fn_with_state(configuration.clone(), trim_trailing_slashes).layer(router);
 
     debug!(
         "Listening for API requests on {}",
         configuration.api_listen_address
     );
-    axum::Server::bind(&configuration.api_listen_address)
-        .serve(router.into_make_service())
-        .with_graceful_shutdown(shutdown_signal())
-        .await
-        .map_err(|error| RVocError::ApiServerError {
-            source: Box::new(error),
-        })?;
+    let shutdown_started = Arc::new(Notify::new());
+    let server = axum::Server::bind(&configuration.api_listen_address)
+        .http1_only(!configuration.api_http2_enabled)
+        .http1_keepalive(configuration.api_http1_keep_alive)
+        .http2_keep_alive_interval(
+            configuration
+                .api_http2_keep_alive_interval
+                .map(|interval| interval.to_std().unwrap()),
+        )
+        .http2_keep_alive_timeout(configuration.api_http2_keep_alive_timeout.to_std().unwrap())
+        .http2_max_concurrent_streams(configuration.api_http2_max_concurrent_streams)
+        .tcp_keepalive(
+            configuration
+                .api_tcp_keep_alive
+                .map(|keep_alive| keep_alive.to_std().unwrap()),
+        )
+        .serve(service.into_make_service())
+        .with_graceful_shutdown({
+            let shutdown_started = shutdown_started.clone();
+            async move {
+                shutdown_signal().await;
+                shutdown_started.notify_one();
+            }
+        });
+    tokio::pin!(server);
+
+    // The server stops accepting new connections when the shutdown signal is received,
+    // but waits for in-flight requests to complete.
+    // We allow this to take at most the shutdown timeout, and then drop the remaining connections.
+    let shutdown_deadline = tokio::select! {
+        result = &mut server => {
+            result.map_err(|error| RVocError::ApiServerError {
+                source: Box::new(error),
+            })?;
+            Instant::now() + configuration.shutdown_timeout.to_std().unwrap()
+        }
+        _ = shutdown_started.notified() => {
+            let shutdown_deadline = Instant::now() + configuration.shutdown_timeout.to_std().unwrap();
+            match tokio::time::timeout_at(shutdown_deadline, &mut server).await {
+                Ok(result) => result.map_err(|error| RVocError::ApiServerError {
+                    source: Box::new(error),
+                })?,
+                Err(_) => warn!(
+                    "In-flight requests did not complete within the shutdown timeout of {}, closing remaining connections",
+                    configuration.shutdown_timeout
+                ),
+            }
+            shutdown_deadline
+        }
+    };
 
     info!("Web API terminated normally");
-    Ok(())
+    Ok(shutdown_deadline)
+}
+
+/// Security headers that are sent with every response, independent of the client.
+#[derive(Debug)]
+struct SecurityHeaders {
+    content_security_policy: HeaderValue,
+    strict_transport_security: Option<HeaderValue>,
+}
+
+impl SecurityHeaders {
+    fn new(configuration: &Configuration) -> RVocResult<Self> {
+        Ok(Self {
+            content_security_policy: HeaderValue::from_str(&configuration.content_security_policy)
+                .map_err(|error| RVocError::MalformedSecurityHeader {
+                    header: "Content-Security-Policy",
+                    source: Box::new(error),
+                })?,
+            strict_transport_security: configuration
+                .strict_transport_security
+                .as_deref()
+                .map(HeaderValue::from_str)
+                .transpose()
+                .map_err(|error| RVocError::MalformedSecurityHeader {
+                    header: "Strict-Transport-Security",
+                    source: Box::new(error),
+                })?,
+        })
+    }
+}
+
+async fn set_security_headers<B>(
+    State(security_headers): State<Arc<SecurityHeaders>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+
+    headers.insert(
+        header::X_CONTENT_TYPE_OPTIONS,
+        HeaderValue::from_static("nosniff"),
+    );
+    headers.insert(header::X_FRAME_OPTIONS, HeaderValue::from_static("DENY"));
+    headers.insert(
+        header::REFERRER_POLICY,
+        HeaderValue::from_static("no-referrer"),
+    );
+    headers.insert(
+        header::CONTENT_SECURITY_POLICY,
+        security_headers.content_security_policy.clone(),
+    );
+    if let Some(strict_transport_security) = &security_headers.strict_transport_security {
+        headers.insert(
+            header::STRICT_TRANSPORT_SECURITY,
+            strict_transport_security.clone(),
+        );
+    }
+
+    response
+}
+
+async fn route_does_not_exist(uri: Uri) -> (StatusCode, Json<ErrorResponse>) {
+    let error = UserError::RouteDoesNotExist {
+        path: uri.path().to_owned(),
+    };
+
+    (
+        error.status_code(),
+        Json(ErrorResponse {
+            error: error.to_string(),
+        }),
+    )
+}
+
+/// Remove trailing slashes from the request path, if enabled in the configuration.
+async fn trim_trailing_slashes<B>(
+    State(configuration): State<Arc<Configuration>>,
+    mut request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let path = request.uri().path();
+    if configuration.api_trim_trailing_slashes && path.len() > 1 && path.ends_with('/') {
+        let trimmed_path = match path.trim_end_matches('/') {
+            "" => "/",
+            trimmed_path => trimmed_path,
+        };
+        let path_and_query = match request.uri().query() {
+            Some(query) => format!("{trimmed_path}?{query}"),
+            None => trimmed_path.to_owned(),
+        };
+
+        let mut uri_parts = request.uri().clone().into_parts();
+        uri_parts.path_and_query = path_and_query.parse().ok();
+        if let Ok(uri) = Uri::from_parts(uri_parts) {
+            *request.uri_mut() = uri;
+        }
+    }
+
+    next.run(request).await
+}
+
+async fn handle_session_layer_error<SessionStoreConnectorError: Display, InnerError: Display>(
+    error: SessionLayerError<SessionStoreConnectorError, InnerError>,
+) -> Response {
+    session_layer_error_response(error)
+}
+
+fn session_layer_error_response<SessionStoreConnectorError: Display, InnerError: Display>(
+    error: SessionLayerError<SessionStoreConnectorError, InnerError>,
+) -> Response {
+    match error {
+        SessionLayerError::SessionStore(
+            typed_session::Error::MaximumSessionIdGenerationTriesReached { .. },
+        ) => RVocError::NoFreeSessionId.into_response(),
+        error => {
+            error!("Session layer error: {error}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
 }
 
 impl IntoResponse for RVocError {
     fn into_response(self) -> axum::response::Response {
-        if let RVocError::UserError(user_error) = self {
-            error!("User error: {user_error:?}");
-            user_error.into_response()
-        } else {
-            error!("Web API error: {self:?}");
+        match self {
+            RVocError::UserError(user_error) => {
+                error!("User error: {user_error:?}");
+                user_error.into_response()
+            }
+            RVocError::NoFreeSessionId => {
+                // The session ids are long enough that collisions are practically impossible,
+                // so this indicates a problem that needs attention.
+                error!("No free session id could be generated: {self:?}. Either MAXIMUM_SESSION_ID_GENERATION_RETRY_COUNT is set too low, or the random number generator is broken");
 
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                StatusCode::SERVICE_UNAVAILABLE.into_response()
+            }
+            error => {
+                error!("Web API error: {error:?}");
+
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
         }
     }
 }
@@ -105,10 +367,29 @@ impl UserError {
     fn status_code(&self) -> StatusCode {
         match self {
             UserError::PasswordLength { .. } => StatusCode::BAD_REQUEST,
+            UserError::CompromisedPassword => StatusCode::BAD_REQUEST,
             UserError::UsernameLength { .. } => StatusCode::BAD_REQUEST,
             UserError::UsernameExists { .. } => StatusCode::CONFLICT,
             UserError::UsernameDoesNotExist { .. } => StatusCode::BAD_REQUEST,
             UserError::InvalidUsernamePassword => StatusCode::BAD_REQUEST,
+            UserError::AccountExpired => StatusCode::FORBIDDEN,
+            UserError::SessionAuthenticationRequired => StatusCode::FORBIDDEN,
+            UserError::RouteDoesNotExist { .. } => StatusCode::NOT_FOUND,
+            UserError::OriginNotAllowed { .. } => StatusCode::FORBIDDEN,
+            UserError::MalformedJson { .. } => StatusCode::BAD_REQUEST,
+            UserError::ApiTokenLabelLength { .. } => StatusCode::BAD_REQUEST,
+            UserError::ApiTokenDoesNotExist { .. } => StatusCode::NOT_FOUND,
+            UserError::WordNoteLength { .. } => StatusCode::BAD_REQUEST,
+            UserError::WordNoteQuotaExceeded { .. } => StatusCode::FORBIDDEN,
+            UserError::WordReportReasonLength { .. } => StatusCode::BAD_REQUEST,
+            UserError::WordReportQuotaExceeded { .. } => StatusCode::FORBIDDEN,
+            UserError::WordReportDoesNotExist { .. } => StatusCode::NOT_FOUND,
+            UserError::CommonWordsLimit { .. } => StatusCode::BAD_REQUEST,
+            UserError::WordBatchSize { .. } => StatusCode::BAD_REQUEST,
+            UserError::WordLength { .. } => StatusCode::BAD_REQUEST,
+            UserError::LanguageDoesNotExist { .. } => StatusCode::NOT_FOUND,
+            UserError::WordDoesNotExist { .. } => StatusCode::NOT_FOUND,
+            UserError::WordNoteDoesNotExist { .. } => StatusCode::NOT_FOUND,
         }
     }
 }
@@ -151,3 +432,40 @@ async fn shutdown_signal() {
 
 type WebConfiguration = Extension<Arc<Configuration>>;
 type WebDatabaseConnectionPool = Extension<RVocAsyncDatabaseConnectionPool>;
+type PasswordRatingRateLimiter = Extension<Arc<RateLimiter>>;
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use axum::{http::StatusCode, response::IntoResponse};
+    use typed_session_axum::SessionLayerError;
+
+    use super::session_layer_error_response;
+    use crate::error::RVocError;
+
+    #[test]
+    fn test_session_id_exhaustion_is_retryable() {
+        assert_eq!(
+            RVocError::NoFreeSessionId.into_response().status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        // This is what the session layer reports if every generated session id collided.
+        let error = SessionLayerError::<RVocError, Infallible>::SessionStore(
+            typed_session::Error::MaximumSessionIdGenerationTriesReached,
+        );
+        assert_eq!(
+            session_layer_error_response(error).status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        let error = SessionLayerError::<RVocError, Infallible>::SessionStore(
+            typed_session::Error::UpdatedSessionDoesNotExist,
+        );
+        assert_eq!(
+            session_layer_error_response(error).status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Limits an action to a fixed number of times per minute, shared between all clients.
+#[derive(Debug)]
+pub struct RateLimiter {
+    maximum_per_minute: u32,
+    window: Mutex<RateLimitWindow>,
+}
+
+#[derive(Debug)]
+struct RateLimitWindow {
+    start: Instant,
+    count: u32,
+}
+
+impl RateLimiter {
+    pub fn new(maximum_per_minute: u32) -> Self {
+        Self {
+            maximum_per_minute,
+            window: Mutex::new(RateLimitWindow {
+                start: Instant::now(),
+                count: 0,
+            }),
+        }
+    }
+
+    /// Returns true and counts the action if the limit of the current minute is not yet reached.
+    pub fn try_acquire(&self) -> bool {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> bool {
+        let mut window = self.window.lock().unwrap();
+
+        if now.duration_since(window.start) >= Duration::from_secs(60) {
+            window.start = now;
+            window.count = 0;
+        }
+
+        if window.count < self.maximum_per_minute {
+            window.count += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::RateLimiter;
+
+    #[test]
+    fn test_rate_limiter() {
+        let rate_limiter = RateLimiter::new(2);
+        let start = rate_limiter.window.lock().unwrap().start;
+        assert!(rate_limiter.try_acquire_at(start));
+        assert!(rate_limiter.try_acquire_at(start));
+        assert!(!rate_limiter.try_acquire_at(start));
+        assert!(!rate_limiter.try_acquire_at(start + Duration::from_secs(59)));
+
+        let next_minute = start + Duration::from_secs(60);
+        assert!(rate_limiter.try_acquire_at(next_minute));
+        assert!(rate_limiter.try_acquire_at(next_minute));
+        assert!(!rate_limiter.try_acquire_at(next_minute));
+
+        let rate_limiter = RateLimiter::new(0);
+        assert!(!rate_limiter.try_acquire_at(Instant::now()));
+    }
+}
@@ -3,8 +3,9 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use diesel::{Insertable, Queryable, Selectable};
+use diesel_async::AsyncPgConnection;
 use thiserror::Error;
-use tracing::trace;
+use tracing::{info, trace};
 use typed_session::{Session, SessionExpiry, SessionId, WriteSessionResult};
 use typed_session_axum::typed_session::SessionStoreConnector;
 
@@ -59,6 +60,8 @@ impl SessionStoreConnector<RVocSessionData> for RVocSessionStoreConnector {
         session_expiry: &SessionExpiry,
         data: &RVocSessionData,
     ) -> Result<WriteSessionResult, typed_session::Error<Self::Error>> {
+        let maximum_sessions_per_user = self.configuration.maximum_sessions_per_user;
+
         match self
             .database_connection_pool
             .execute_transaction::<_, TryInsertSessionError>(
@@ -67,6 +70,15 @@ impl SessionStoreConnector<RVocSessionData> for RVocSessionStoreConnector {
                         use crate::database::schema::sessions::dsl::*;
                         use diesel_async::RunQueryDsl;
 
+                        if let RVocSessionData::LoggedIn(user) = data {
+                            evict_least_recently_used_sessions(
+                                user,
+                                maximum_sessions_per_user,
+                                database_connection,
+                            )
+                            .await?;
+                        }
+
                         RVocSessionInsertable::new(current_id, session_expiry, data)
                             .insert_into(sessions)
                             .execute(database_connection)
@@ -163,6 +175,8 @@ impl SessionStoreConnector<RVocSessionData> for RVocSessionStoreConnector {
         session_expiry: &SessionExpiry,
         data: &RVocSessionData,
     ) -> Result<WriteSessionResult, typed_session::Error<Self::Error>> {
+        let maximum_sessions_per_user = self.configuration.maximum_sessions_per_user;
+
         match self
             .database_connection_pool
             .execute_transaction::<_, TryInsertSessionError>(
@@ -184,6 +198,15 @@ impl SessionStoreConnector<RVocSessionData> for RVocSessionStoreConnector {
                             ));
                         }
 
+                        if let RVocSessionData::LoggedIn(user) = data {
+                            evict_least_recently_used_sessions(
+                                user,
+                                maximum_sessions_per_user,
+                                database_connection,
+                            )
+                            .await?;
+                        }
+
                         RVocSessionInsertable::new(current_id, session_expiry, data)
                             .insert_into(sessions)
                             .execute(database_connection)
@@ -277,6 +300,48 @@ impl SessionStoreConnector<RVocSessionData> for RVocSessionStoreConnector {
     }
 }
 
+/// Delete the least recently used sessions of the given user,
+/// such that the user has at most `maximum_sessions_per_user` sessions after inserting one more.
+/// Expired sessions are not counted, since they are deleted by a separate job anyways.
+///
+/// Since updating a session inserts a new row, the creation time of a session is the time it was last renewed.
+/// This is at most [`Configuration::session_minimum_renewal_interval`] before it was last used.
+async fn evict_least_recently_used_sessions(
+    user: &Username,
+    maximum_sessions_per_user: usize,
+    database_connection: &mut AsyncPgConnection,
+) -> Result<(), diesel::result::Error> {
+    use crate::database::schema::sessions::dsl::*;
+    use diesel::dsl::now;
+    use diesel::ExpressionMethods;
+    use diesel::QueryDsl;
+    use diesel_async::RunQueryDsl;
+
+    let evicted_session_ids: Vec<Vec<u8>> = sessions
+        .select(id)
+        .filter(username.eq(user.as_ref()))
+        .filter(expiry.gt(now))
+        .order_by((created_at.desc(), expiry.desc()))
+        .offset(
+            maximum_sessions_per_user
+                .saturating_sub(1)
+                .try_into()
+                .unwrap_or(i64::MAX),
+        )
+        .load(database_connection)
+        .await?;
+
+    if !evicted_session_ids.is_empty() {
+        let evicted_session_count = diesel::delete(sessions)
+            .filter(id.eq_any(evicted_session_ids))
+            .execute(database_connection)
+            .await?;
+        info!("Evicted {evicted_session_count} least recently used sessions of user {user:?}");
+    }
+
+    Ok(())
+}
+
 #[derive(Insertable, Debug)]
 #[diesel(table_name = crate::database::schema::sessions)]
 #[diesel(primary_key(id))]
@@ -0,0 +1,10 @@
+use api_commands::ServerTime;
+use chrono::Utc;
+
+use super::json::Json;
+
+/// Return the current time of the server, so that clients can compensate for clock skew,
+/// e.g. when computing when the session cookie expires.
+pub async fn server_time() -> Json<ServerTime> {
+    Json(ServerTime { time: Utc::now() })
+}
@@ -1,29 +1,41 @@
 use crate::{
+    database::user_sessions::delete_sessions_of_user_in_batches,
     error::{RVocError, RVocResult, UserError},
-    model::user::{password_hash::PasswordHash, username::Username, User},
+    model::user::{
+        compromised_password::CompromisedPasswordCheck, password_hash::PasswordHash,
+        username::Username, User,
+    },
 };
-use api_commands::CreateAccount;
-use axum::{http::StatusCode, Extension, Json};
-use tracing::instrument;
+use api_commands::{AccountSecurity, CreateAccount, PasswordRating, RatePassword, WhoAmI};
+use axum::{http::StatusCode, Extension};
+use tracing::{debug, instrument};
 use typed_session_axum::WritableSession;
 
 use super::{
-    authentication::LoggedInUser, session::RVocSessionData, WebConfiguration,
-    WebDatabaseConnectionPool,
+    authentication::LoggedInUser, json::Json, session::RVocSessionData, PasswordRatingRateLimiter,
+    WebConfiguration, WebDatabaseConnectionPool,
 };
 
-#[instrument(err, skip(database_connection_pool, configuration))]
+#[instrument(
+    err,
+    skip(database_connection_pool, configuration, compromised_password_check)
+)]
 pub async fn create_account(
     Extension(database_connection_pool): WebDatabaseConnectionPool,
     Extension(configuration): WebConfiguration,
+    Extension(compromised_password_check): Extension<CompromisedPasswordCheck>,
     Json(create_account): Json<CreateAccount>,
 ) -> RVocResult<StatusCode> {
     let CreateAccount { username, password } = create_account;
     let username = Username::new(username, &configuration)?;
+    configuration.verify_password_length(&password)?;
+    compromised_password_check
+        .verify_password_not_compromised(&password, &configuration)
+        .await?;
 
     let user = User {
         name: username,
-        password_hash: PasswordHash::new(password, &configuration)?,
+        password_hash: PasswordHash::new_async(password, configuration.clone()).await?,
     };
 
     database_connection_pool
@@ -70,6 +82,147 @@ pub async fn create_account(
         })
 }
 
+/// Check a password against the password policy, so that clients can give feedback before creating an account.
+/// The password is neither stored nor logged.
+///
+/// This endpoint does not require authentication, so the breach checks it triggers are rate limited.
+#[instrument(skip(configuration, compromised_password_check, rate_limiter, rate_password))]
+pub async fn rate_password(
+    Extension(configuration): WebConfiguration,
+    Extension(compromised_password_check): Extension<CompromisedPasswordCheck>,
+    Extension(rate_limiter): PasswordRatingRateLimiter,
+    Json(rate_password): Json<RatePassword>,
+) -> Json<PasswordRating> {
+    let RatePassword { password } = rate_password;
+    let password_length = password.unsecure().len();
+    let too_short = password_length < configuration.minimum_password_length;
+    let too_long = password_length > configuration.maximum_password_length;
+
+    // Do not send passwords to the breach check that would be rejected anyways,
+    // and do not count ratings against the rate limit if the breach check is disabled.
+    let compromised = if too_short || too_long || !configuration.hibp_check_enabled {
+        None
+    } else if rate_limiter.try_acquire() {
+        compromised_password_check
+            .is_password_compromised(&password, &configuration)
+            .await
+    } else {
+        debug!("Skipping the breach check of a password rating because the rate limit is reached");
+        None
+    };
+
+    Json(PasswordRating {
+        acceptable: !too_short && !too_long && compromised != Some(true),
+        too_short,
+        too_long,
+        compromised,
+    })
+}
+
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn whoami(
+    Extension(username): Extension<LoggedInUser>,
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+) -> RVocResult<Json<WhoAmI>> {
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::users;
+                    use diesel::ExpressionMethods;
+                    use diesel::OptionalExtension;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let last_login_at = users::table
+                        .select(users::last_login_at)
+                        .filter(users::name.eq(username.as_ref()))
+                        .first(database_connection)
+                        .await
+                        .optional()?
+                        .ok_or_else(|| UserError::UsernameDoesNotExist {
+                            username: username.clone().into(),
+                        })?;
+
+                    Ok(Json(WhoAmI {
+                        username: username.clone().into(),
+                        last_login_at,
+                    }))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::WhoAmI {
+                source: Box::new(error),
+            },
+        })
+}
+
+/// Summarise the login state of the logged in user, for a security settings page.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn account_security(
+    Extension(username): Extension<LoggedInUser>,
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+) -> RVocResult<Json<AccountSecurity>> {
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::{api_tokens, sessions, users};
+                    use diesel::dsl::now;
+                    use diesel::BoolExpressionMethods;
+                    use diesel::ExpressionMethods;
+                    use diesel::OptionalExtension;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let last_login_at = users::table
+                        .select(users::last_login_at)
+                        .filter(users::name.eq(username.as_ref()))
+                        .first(database_connection)
+                        .await
+                        .optional()?
+                        .ok_or_else(|| UserError::UsernameDoesNotExist {
+                            username: username.clone().into(),
+                        })?;
+
+                    let active_session_count: i64 = sessions::table
+                        .filter(sessions::username.eq(username.as_ref()))
+                        .filter(sessions::expiry.gt(now))
+                        .count()
+                        .get_result(database_connection)
+                        .await?;
+
+                    let api_token_count: i64 = api_tokens::table
+                        .filter(api_tokens::username.eq(username.as_ref()))
+                        .filter(api_tokens::expiry.is_null().or(api_tokens::expiry.gt(now)))
+                        .count()
+                        .get_result(database_connection)
+                        .await?;
+
+                    Ok(Json(AccountSecurity {
+                        last_login_at,
+                        active_session_count: active_session_count.try_into().unwrap(),
+                        api_token_count: api_token_count.try_into().unwrap(),
+                    }))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::AccountSecurity {
+                source: Box::new(error),
+            },
+        })
+}
+
 #[instrument(err, skip(database_connection_pool))]
 pub async fn delete_account(
     Extension(username): Extension<LoggedInUser>,
@@ -79,6 +232,15 @@ pub async fn delete_account(
 ) -> RVocResult<StatusCode> {
     session.delete();
 
+    // If this is interrupted, the user still exists and can log in again,
+    // so the account is not left in a broken state.
+    delete_sessions_of_user_in_batches(
+        username.as_ref(),
+        &database_connection_pool,
+        &configuration,
+    )
+    .await?;
+
     database_connection_pool
         .execute_transaction(
             |database_connection| {
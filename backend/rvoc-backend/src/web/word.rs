@@ -0,0 +1,711 @@
+use std::collections::HashSet;
+
+use api_commands::{
+    DetectLanguage, LanguageWord, ListCommonWords, ListTranslations, RandomWord, ReportWord,
+    SetWordNote, WordIdentifier, WordNote,
+};
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use diesel_async::AsyncPgConnection;
+use rand::Rng;
+use tracing::instrument;
+
+use crate::{
+    database::transactions::TransactionError,
+    error::{RVocError, RVocResult, UserError},
+};
+
+use super::{
+    authentication::LoggedInUser, json::Json, WebConfiguration, WebDatabaseConnectionPool,
+};
+
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn set_word_note(
+    Extension(username): Extension<LoggedInUser>,
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Json(set_word_note): Json<SetWordNote>,
+) -> RVocResult<StatusCode> {
+    let SetWordNote { word, note } = set_word_note;
+    configuration.verify_word_note_length(&note)?;
+
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::user_word_notes;
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let (word_type, language) = find_word(&word, database_connection).await?;
+
+                    // Updating an existing note does not count towards the quota.
+                    let note_exists: bool = diesel::select(diesel::dsl::exists(
+                        user_word_notes::table
+                            .filter(user_word_notes::username.eq(username.as_ref()))
+                            .filter(user_word_notes::word.eq(&word.word))
+                            .filter(user_word_notes::word_type.eq(word_type))
+                            .filter(user_word_notes::language.eq(language)),
+                    ))
+                    .get_result(database_connection)
+                    .await?;
+
+                    if !note_exists {
+                        let note_count: i64 = user_word_notes::table
+                            .filter(user_word_notes::username.eq(username.as_ref()))
+                            .count()
+                            .get_result(database_connection)
+                            .await?;
+
+                        if note_count
+                            >= configuration
+                                .maximum_word_notes_per_user
+                                .try_into()
+                                .unwrap_or(i64::MAX)
+                        {
+                            return Err(UserError::WordNoteQuotaExceeded {
+                                maximum: configuration.maximum_word_notes_per_user,
+                            }
+                            .into());
+                        }
+                    }
+
+                    diesel::insert_into(user_word_notes::table)
+                        .values((
+                            user_word_notes::username.eq(username.as_ref()),
+                            user_word_notes::word.eq(&word.word),
+                            user_word_notes::word_type.eq(word_type),
+                            user_word_notes::language.eq(language),
+                            user_word_notes::note.eq(&note),
+                        ))
+                        .on_conflict((
+                            user_word_notes::username,
+                            user_word_notes::word,
+                            user_word_notes::word_type,
+                            user_word_notes::language,
+                        ))
+                        .do_update()
+                        .set(user_word_notes::note.eq(&note))
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(StatusCode::NO_CONTENT)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::SetWordNote {
+                source: Box::new(error),
+            },
+        })
+}
+
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn delete_word_note(
+    Extension(username): Extension<LoggedInUser>,
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Query(word): Query<WordIdentifier>,
+) -> RVocResult<StatusCode> {
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::user_word_notes;
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let (word_type, language) = find_word(&word, database_connection).await?;
+
+                    let affected_rows = diesel::delete(user_word_notes::table)
+                        .filter(user_word_notes::username.eq(username.as_ref()))
+                        .filter(user_word_notes::word.eq(&word.word))
+                        .filter(user_word_notes::word_type.eq(word_type))
+                        .filter(user_word_notes::language.eq(language))
+                        .execute(database_connection)
+                        .await?;
+
+                    if affected_rows == 0 {
+                        Err(UserError::WordNoteDoesNotExist { word: word.clone() }.into())
+                    } else {
+                        Ok(StatusCode::NO_CONTENT)
+                    }
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::DeleteWordNote {
+                source: Box::new(error),
+            },
+        })
+}
+
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn get_word_note(
+    Extension(username): Extension<LoggedInUser>,
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Query(word): Query<WordIdentifier>,
+) -> RVocResult<Json<WordNote>> {
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::user_word_notes;
+                    use diesel::ExpressionMethods;
+                    use diesel::OptionalExtension;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let (word_type, language) = find_word(&word, database_connection).await?;
+
+                    let note = user_word_notes::table
+                        .select(user_word_notes::note)
+                        .filter(user_word_notes::username.eq(username.as_ref()))
+                        .filter(user_word_notes::word.eq(&word.word))
+                        .filter(user_word_notes::word_type.eq(word_type))
+                        .filter(user_word_notes::language.eq(language))
+                        .first(database_connection)
+                        .await
+                        .optional()?
+                        .ok_or_else(|| UserError::WordNoteDoesNotExist { word: word.clone() })?;
+
+                    Ok(Json(WordNote { note }))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::GetWordNote {
+                source: Box::new(error),
+            },
+        })
+}
+
+/// Report a problem with the dictionary data.
+/// The reported word does not need to exist, since it may be missing from the data.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn report_word(
+    Extension(username): Extension<LoggedInUser>,
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Json(report_word): Json<ReportWord>,
+) -> RVocResult<StatusCode> {
+    let ReportWord { word, reason } = report_word;
+    configuration.verify_word_length(&word.language)?;
+    configuration.verify_word_length(&word.word)?;
+    configuration.verify_word_length(&word.word_type)?;
+    configuration.verify_word_report_reason_length(&reason)?;
+
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::word_reports;
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let open_report_count: i64 = word_reports::table
+                        .filter(word_reports::username.eq(username.as_ref()))
+                        .filter(word_reports::resolved_at.is_null())
+                        .count()
+                        .get_result(database_connection)
+                        .await?;
+
+                    if open_report_count
+                        >= configuration
+                            .maximum_open_word_reports_per_user
+                            .try_into()
+                            .unwrap_or(i64::MAX)
+                    {
+                        return Err(UserError::WordReportQuotaExceeded {
+                            maximum: configuration.maximum_open_word_reports_per_user,
+                        }
+                        .into());
+                    }
+
+                    diesel::insert_into(word_reports::table)
+                        .values((
+                            word_reports::username.eq(username.as_ref()),
+                            word_reports::language.eq(&word.language),
+                            word_reports::word.eq(&word.word),
+                            word_reports::word_type.eq(&word.word_type),
+                            word_reports::reason.eq(&reason),
+                        ))
+                        .execute(database_connection)
+                        .await?;
+
+                    Ok(StatusCode::CREATED)
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::ReportWord {
+                source: Box::new(error),
+            },
+        })
+}
+
+/// List the words of a language ordered by their frequency rank, most common first.
+/// Words without a frequency rank come last.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn list_common_words(
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Query(list_common_words): Query<ListCommonWords>,
+) -> RVocResult<Json<Vec<WordIdentifier>>> {
+    let ListCommonWords { language, limit } = list_common_words;
+    configuration.verify_common_words_limit(limit)?;
+
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::{languages, word_types, words};
+                    use diesel::ExpressionMethods;
+                    use diesel::OptionalExtension;
+                    use diesel::PgSortExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let language_id: i32 = languages::table
+                        .select(languages::id)
+                        .filter(languages::english_name.eq(&language))
+                        .first(database_connection)
+                        .await
+                        .optional()?
+                        .ok_or_else(|| UserError::LanguageDoesNotExist {
+                            language: language.clone(),
+                        })?;
+
+                    let common_words: Vec<(String, String)> = words::table
+                        .inner_join(word_types::table)
+                        .select((words::word, word_types::english_name))
+                        .filter(words::language.eq(language_id))
+                        .order_by((
+                            words::frequency_rank.asc().nulls_last(),
+                            words::word.asc(),
+                            words::word_type.asc(),
+                        ))
+                        .limit(limit.try_into().unwrap_or(i64::MAX))
+                        .load(database_connection)
+                        .await?;
+
+                    Ok(Json(
+                        common_words
+                            .into_iter()
+                            .map(|(word, word_type)| WordIdentifier {
+                                language: language.clone(),
+                                word,
+                                word_type,
+                            })
+                            .collect(),
+                    ))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::ListCommonWords {
+                source: Box::new(error),
+            },
+        })
+}
+
+/// Check for each of the given words if it exists with any word type.
+/// The result contains one boolean per word, in the same order.
+#[instrument(err, skip(database_connection_pool, configuration, language_words))]
+pub async fn check_words_exist(
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Json(language_words): Json<Vec<LanguageWord>>,
+) -> RVocResult<Json<Vec<bool>>> {
+    configuration.verify_word_batch_size(language_words.len())?;
+    for language_word in &language_words {
+        configuration.verify_word_length(&language_word.language)?;
+        configuration.verify_word_length(&language_word.word)?;
+    }
+
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::{languages, words};
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    // Select all combinations of the requested spellings and languages in a single query,
+                    // and then check which of the requested pairs are among them.
+                    let existing_words: HashSet<(String, String)> = words::table
+                        .inner_join(languages::table)
+                        .select((languages::english_name, words::word))
+                        .filter(
+                            words::word.eq_any(
+                                language_words
+                                    .iter()
+                                    .map(|language_word| language_word.word.as_str())
+                                    .collect::<Vec<_>>(),
+                            ),
+                        )
+                        .filter(
+                            languages::english_name.eq_any(
+                                language_words
+                                    .iter()
+                                    .map(|language_word| language_word.language.as_str())
+                                    .collect::<Vec<_>>(),
+                            ),
+                        )
+                        .distinct()
+                        .load::<(String, String)>(database_connection)
+                        .await?
+                        .into_iter()
+                        .collect();
+
+                    Ok(Json(
+                        language_words
+                            .iter()
+                            .map(|language_word| {
+                                existing_words.contains(&(
+                                    language_word.language.clone(),
+                                    language_word.word.clone(),
+                                ))
+                            })
+                            .collect(),
+                    ))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::CheckWordsExist {
+                source: Box::new(error),
+            },
+        })
+}
+
+/// Look up the base forms of a possibly inflected word, e.g. "run" for "running".
+/// Words that are not known as an inflected form have no base forms.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn find_lemmas(
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Query(language_word): Query<LanguageWord>,
+) -> RVocResult<Json<Vec<String>>> {
+    configuration.verify_word_length(&language_word.language)?;
+    configuration.verify_word_length(&language_word.word)?;
+
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::{languages, word_forms};
+                    use diesel::ExpressionMethods;
+                    use diesel::OptionalExtension;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let language_id: i32 = languages::table
+                        .select(languages::id)
+                        .filter(languages::english_name.eq(&language_word.language))
+                        .first(database_connection)
+                        .await
+                        .optional()?
+                        .ok_or_else(|| UserError::LanguageDoesNotExist {
+                            language: language_word.language.clone(),
+                        })?;
+
+                    let lemmas = word_forms::table
+                        .select(word_forms::lemma)
+                        .filter(word_forms::language.eq(language_id))
+                        .filter(word_forms::form.eq(&language_word.word))
+                        .order_by(word_forms::lemma)
+                        .load(database_connection)
+                        .await?;
+
+                    Ok(Json(lemmas))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::FindLemmas {
+                source: Box::new(error),
+            },
+        })
+}
+
+/// List the translations of a word into another language, for all word types of the word.
+/// Words without known translations have no translations.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn list_translations(
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Path((language, word)): Path<(String, String)>,
+    Query(list_translations): Query<ListTranslations>,
+) -> RVocResult<Json<Vec<WordIdentifier>>> {
+    let ListTranslations { to } = list_translations;
+    configuration.verify_word_length(&language)?;
+    configuration.verify_word_length(&word)?;
+    configuration.verify_word_length(&to)?;
+
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::{translations, word_types};
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let language_id = find_language(&language, database_connection).await?;
+                    let to_language_id = find_language(&to, database_connection).await?;
+
+                    let translations: Vec<(String, String)> = translations::table
+                        .inner_join(
+                            word_types::table
+                                .on(word_types::id.eq(translations::translation_word_type)),
+                        )
+                        .select((translations::translation_word, word_types::english_name))
+                        .filter(translations::language.eq(language_id))
+                        .filter(translations::word.eq(&word))
+                        .filter(translations::translation_language.eq(to_language_id))
+                        .distinct()
+                        .order_by((translations::translation_word, word_types::english_name))
+                        .load(database_connection)
+                        .await?;
+
+                    Ok(Json(
+                        translations
+                            .into_iter()
+                            .map(|(word, word_type)| WordIdentifier {
+                                language: to.clone(),
+                                word,
+                                word_type,
+                            })
+                            .collect(),
+                    ))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::ListTranslations {
+                source: Box::new(error),
+            },
+        })
+}
+
+/// Guess the language of a word by listing all languages that contain it.
+/// Languages in which the word is more common come first.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn detect_language(
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Query(detect_language): Query<DetectLanguage>,
+) -> RVocResult<Json<Vec<String>>> {
+    let DetectLanguage { word } = detect_language;
+    configuration.verify_word_length(&word)?;
+
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::{languages, words};
+                    use diesel::dsl::min;
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    // A word may have multiple word types, so use its most common one.
+                    let mut candidates: Vec<(String, Option<i32>)> = words::table
+                        .inner_join(languages::table)
+                        .filter(words::word.eq(&word))
+                        .group_by(languages::english_name)
+                        .select((languages::english_name, min(words::frequency_rank)))
+                        .load(database_connection)
+                        .await?;
+
+                    candidates.sort_by(|(language_a, rank_a), (language_b, rank_b)| {
+                        // Languages without a frequency rank for the word come last.
+                        (rank_a.is_none(), rank_a, language_a).cmp(&(
+                            rank_b.is_none(),
+                            rank_b,
+                            language_b,
+                        ))
+                    });
+
+                    Ok(Json(
+                        candidates
+                            .into_iter()
+                            .map(|(language, _)| language)
+                            .collect(),
+                    ))
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::DetectLanguage {
+                source: Box::new(error),
+            },
+        })
+}
+
+/// Select a random word matching the given filters.
+/// If no word matches, then `204 No Content` is returned.
+#[instrument(err, skip(database_connection_pool, configuration))]
+pub async fn random_word(
+    Extension(database_connection_pool): WebDatabaseConnectionPool,
+    Extension(configuration): WebConfiguration,
+    Query(random_word): Query<RandomWord>,
+) -> RVocResult<Response> {
+    let RandomWord {
+        language,
+        word_type,
+    } = random_word;
+    for filter in language.iter().chain(&word_type) {
+        configuration.verify_word_length(filter)?;
+    }
+
+    database_connection_pool
+        .execute_transaction::<_, RVocError>(
+            |database_connection| {
+                Box::pin(async {
+                    use crate::database::schema::{languages, word_types, words};
+                    use diesel::ExpressionMethods;
+                    use diesel::QueryDsl;
+                    use diesel_async::RunQueryDsl;
+
+                    let matching_words = || {
+                        let mut query = words::table
+                            .inner_join(languages::table)
+                            .inner_join(word_types::table)
+                            .into_boxed();
+                        if let Some(language) = &language {
+                            query = query.filter(languages::english_name.eq(language));
+                        }
+                        if let Some(word_type) = &word_type {
+                            query = query.filter(word_types::english_name.eq(word_type));
+                        }
+                        query
+                    };
+
+                    // Skip to a random row instead of sorting all matching rows randomly.
+                    let count: i64 = matching_words()
+                        .count()
+                        .get_result(database_connection)
+                        .await?;
+                    if count == 0 {
+                        return Ok(StatusCode::NO_CONTENT.into_response());
+                    }
+                    let offset = rand::thread_rng().gen_range(0..count);
+
+                    let (word, language, word_type) = matching_words()
+                        .select((
+                            words::word,
+                            languages::english_name,
+                            word_types::english_name,
+                        ))
+                        .order_by((words::word, words::word_type, words::language))
+                        .offset(offset)
+                        .first(database_connection)
+                        .await?;
+
+                    Ok(Json(WordIdentifier {
+                        language,
+                        word,
+                        word_type,
+                    })
+                    .into_response())
+                })
+            },
+            configuration.maximum_transaction_retry_count,
+        )
+        .await
+        .map_err(|error| match error {
+            error @ RVocError::UserError(_) => error,
+            error => RVocError::RandomWord {
+                source: Box::new(error),
+            },
+        })
+}
+
+/// Look up the id of the language with the given english name.
+/// If the language does not exist, then [`UserError::LanguageDoesNotExist`] is returned.
+async fn find_language(
+    language: &str,
+    database_connection: &mut AsyncPgConnection,
+) -> Result<i32, TransactionError<RVocError>> {
+    use crate::database::schema::languages;
+    use diesel::ExpressionMethods;
+    use diesel::OptionalExtension;
+    use diesel::QueryDsl;
+    use diesel_async::RunQueryDsl;
+
+    languages::table
+        .select(languages::id)
+        .filter(languages::english_name.eq(language))
+        .first(database_connection)
+        .await
+        .optional()?
+        .ok_or_else(|| {
+            UserError::LanguageDoesNotExist {
+                language: language.to_owned(),
+            }
+            .into()
+        })
+}
+
+/// Look up the word type id and the language id of the given word.
+/// If the word does not exist, then [`UserError::WordDoesNotExist`] is returned.
+async fn find_word(
+    word: &WordIdentifier,
+    database_connection: &mut AsyncPgConnection,
+) -> Result<(i32, i32), TransactionError<RVocError>> {
+    use crate::database::schema::{languages, word_types, words};
+    use diesel::ExpressionMethods;
+    use diesel::OptionalExtension;
+    use diesel::QueryDsl;
+    use diesel_async::RunQueryDsl;
+
+    words::table
+        .inner_join(languages::table)
+        .inner_join(word_types::table)
+        .select((words::word_type, words::language))
+        .filter(words::word.eq(&word.word))
+        .filter(languages::english_name.eq(&word.language))
+        .filter(word_types::english_name.eq(&word.word_type))
+        .first(database_connection)
+        .await
+        .optional()?
+        .ok_or_else(|| UserError::WordDoesNotExist { word: word.clone() }.into())
+}
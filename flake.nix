@@ -31,6 +31,8 @@
         filter = path: type:
           # Allow sql files for migrations
           (lib.hasSuffix "\.sql" path) ||
+          # Allow the wiktionary dump fixture of the internal integration tests
+          (lib.hasSuffix "\.xml\.bz2" path) ||
           # Default filter from crane (allow .rs files)
           (craneLib.filterCargoSources path type)
         ;